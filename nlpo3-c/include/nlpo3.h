@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * C API for nlpO3, a Thai natural language processing library.
+ *
+ * Generated by cbindgen from nlpo3-c/src/lib.rs, do not edit.
+ */
+
+#ifndef NLPO3_H
+#define NLPO3_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status code returned by all API functions.
+ */
+typedef enum Nlpo3Status {
+  /**
+   * Success
+   */
+  NLPO3_STATUS_OK = 0,
+  /**
+   * A required pointer argument is NULL
+   */
+  NLPO3_STATUS_NULL_POINTER = 1,
+  /**
+   * A string argument is not valid UTF-8
+   */
+  NLPO3_STATUS_INVALID_UTF8 = 2,
+  /**
+   * The dictionary file cannot be read
+   */
+  NLPO3_STATUS_IO = 3,
+  /**
+   * Segmentation failed
+   */
+  NLPO3_STATUS_SEGMENT = 4,
+  /**
+   * Unexpected internal error
+   */
+  NLPO3_STATUS_PANIC = 5,
+  /**
+   * The dictionary file is not a valid dictionary
+   */
+  NLPO3_STATUS_INVALID_DICTIONARY = 6,
+} Nlpo3Status;
+
+/**
+ * How a token was found by the tokenizer.
+ */
+typedef enum Nlpo3TokenKind {
+  /**
+   * A word from the dictionary
+   */
+  NLPO3_TOKEN_KIND_DICTIONARY = 0,
+  /**
+   * Not in the dictionary, matched as non-Thai text
+   */
+  NLPO3_TOKEN_KIND_NON_THAI = 1,
+  /**
+   * Thai text not in the dictionary
+   */
+  NLPO3_TOKEN_KIND_UNKNOWN = 2,
+} Nlpo3TokenKind;
+
+/**
+ * Opaque tokenizer handle, freed with nlpo3_tokenizer_free().
+ *
+ * A tokenizer can be used from several threads at the same time.
+ * Segmentation waits for dictionary changes to finish, and vice versa.
+ */
+typedef struct Nlpo3Tokenizer Nlpo3Tokenizer;
+
+/**
+ * A token and its position in the input text.
+ */
+typedef struct Nlpo3Token {
+  /**
+   * Token text
+   */
+  char *text;
+  /**
+   * Byte offset of the token start in the input text
+   */
+  size_t start;
+  /**
+   * Byte offset of the token end (exclusive) in the input text
+   */
+  size_t end;
+  enum Nlpo3TokenKind kind;
+} Nlpo3Token;
+
+/**
+ * Tokens of a text, freed with nlpo3_token_array_free().
+ */
+typedef struct Nlpo3TokenArray {
+  struct Nlpo3Token *tokens;
+  size_t len;
+} Nlpo3TokenArray;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a tokenizer using a dictionary file, one word per line.
+ *
+ * # Safety
+ * `path` must be a NUL-terminated string and `out` a valid pointer.
+ */
+enum Nlpo3Status nlpo3_tokenizer_new_from_path(const char *path, struct Nlpo3Tokenizer **out);
+
+/**
+ * Create a tokenizer using an array of `len` words.
+ *
+ * # Safety
+ * `words` must point to `len` NUL-terminated strings
+ * (or may be NULL if `len` is 0), and `out` must be a valid pointer.
+ */
+enum Nlpo3Status nlpo3_tokenizer_new_from_words(const char *const *words,
+                                                size_t len,
+                                                struct Nlpo3Tokenizer **out);
+
+/**
+ * Free a tokenizer. Does nothing if `tokenizer` is NULL.
+ *
+ * # Safety
+ * `tokenizer` must be NULL or created by this library and not freed yet.
+ */
+void nlpo3_tokenizer_free(struct Nlpo3Tokenizer *tokenizer);
+
+/**
+ * Segment `text` into tokens, with byte offsets into `text`.
+ * On success, `out` must be freed with nlpo3_token_array_free().
+ *
+ * # Safety
+ * `tokenizer` must be a valid tokenizer, `text` a NUL-terminated string,
+ * and `out` a valid pointer.
+ */
+enum Nlpo3Status nlpo3_tokenizer_segment(const struct Nlpo3Tokenizer *tokenizer,
+                                         const char *text,
+                                         bool safe,
+                                         bool parallel,
+                                         struct Nlpo3TokenArray *out);
+
+/**
+ * Free the tokens of a token array, and set it to an empty array.
+ * Does nothing if `array` is NULL.
+ *
+ * # Safety
+ * `array` must be NULL or filled by nlpo3_tokenizer_segment() and not freed yet.
+ */
+void nlpo3_token_array_free(struct Nlpo3TokenArray *array);
+
+/**
+ * Add an array of `len` words to the dictionary.
+ *
+ * # Safety
+ * `tokenizer` must be a valid tokenizer, and `words` must point to
+ * `len` NUL-terminated strings (or may be NULL if `len` is 0).
+ */
+enum Nlpo3Status nlpo3_tokenizer_add_word(struct Nlpo3Tokenizer *tokenizer,
+                                          const char *const *words,
+                                          size_t len);
+
+/**
+ * Remove an array of `len` words from the dictionary.
+ *
+ * # Safety
+ * `tokenizer` must be a valid tokenizer, and `words` must point to
+ * `len` NUL-terminated strings (or may be NULL if `len` is 0).
+ */
+enum Nlpo3Status nlpo3_tokenizer_remove_word(struct Nlpo3Tokenizer *tokenizer,
+                                             const char *const *words,
+                                             size_t len);
+
+/**
+ * Message of the last failed call on this thread, or NULL if none.
+ * The message is valid until the next failed call on this thread.
+ */
+const char *nlpo3_last_error_message(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NLPO3_H */
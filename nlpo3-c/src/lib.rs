@@ -0,0 +1,448 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * C API for nlpO3, a Thai natural language processing library.
+ *
+ * All functions return an Nlpo3Status. On failure, a message
+ * is available from nlpo3_last_error_message() on the same thread.
+ * Strings are NUL-terminated UTF-8.
+ *
+ * The header include/nlpo3.h is generated from this file by cbindgen.
+ */
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::io;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::RwLock;
+
+use nlpo3::tokenizer::newmm::NewmmTokenizer;
+use nlpo3::tokenizer::token::TokenKind;
+use nlpo3::tokenizer::DictSource;
+
+/// Status code returned by all API functions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nlpo3Status {
+    /// Success
+    Ok = 0,
+    /// A required pointer argument is NULL
+    NullPointer = 1,
+    /// A string argument is not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The dictionary file cannot be read
+    Io = 3,
+    /// Segmentation failed
+    Segment = 4,
+    /// Unexpected internal error
+    Panic = 5,
+    /// The dictionary file is not a valid dictionary
+    InvalidDictionary = 6,
+}
+
+/// How a token was found by the tokenizer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nlpo3TokenKind {
+    /// A word from the dictionary
+    Dictionary = 0,
+    /// Not in the dictionary, matched as non-Thai text
+    NonThai = 1,
+    /// Thai text not in the dictionary
+    Unknown = 2,
+}
+
+/// A token and its position in the input text.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Nlpo3Token {
+    /// Token text
+    pub text: *mut c_char,
+    /// Byte offset of the token start in the input text
+    pub start: usize,
+    /// Byte offset of the token end (exclusive) in the input text
+    pub end: usize,
+    pub kind: Nlpo3TokenKind,
+}
+
+/// Tokens of a text, freed with nlpo3_token_array_free().
+#[repr(C)]
+#[derive(Debug)]
+pub struct Nlpo3TokenArray {
+    pub tokens: *mut Nlpo3Token,
+    pub len: usize,
+}
+
+/// Opaque tokenizer handle, freed with nlpo3_tokenizer_free().
+///
+/// A tokenizer can be used from several threads at the same time.
+/// Segmentation waits for dictionary changes to finish, and vice versa.
+pub struct Nlpo3Tokenizer {
+    tokenizer: RwLock<NewmmTokenizer>,
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: Nlpo3Status,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: Nlpo3Status, message: String) -> Self {
+        ApiError { status, message }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // a message with a NUL byte is cut at the NUL byte
+    let message = message.split('\0').next().unwrap_or_default();
+    LAST_ERROR.with(|last_error| {
+        *last_error.borrow_mut() = CString::new(message).ok();
+    });
+}
+
+/// Run an API function body, converting errors and panics to a status.
+fn run<F: FnOnce() -> Result<(), ApiError>>(body: F) -> Nlpo3Status {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => Nlpo3Status::Ok,
+        Ok(Err(error)) => {
+            set_last_error(&error.message);
+            error.status
+        }
+        Err(_) => {
+            set_last_error("Unexpected internal error.");
+            Nlpo3Status::Panic
+        }
+    }
+}
+
+fn check_not_null<T>(pointer: *const T, name: &str) -> Result<(), ApiError> {
+    if pointer.is_null() {
+        Err(ApiError::new(
+            Nlpo3Status::NullPointer,
+            format!("Argument {} is NULL.", name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// # Safety
+/// `string` must be NULL or a NUL-terminated string that outlives `'a`.
+unsafe fn to_str<'a>(string: *const c_char, name: &str) -> Result<&'a str, ApiError> {
+    check_not_null(string, name)?;
+    CStr::from_ptr(string).to_str().map_err(|error| {
+        ApiError::new(
+            Nlpo3Status::InvalidUtf8,
+            format!("Argument {} is not valid UTF-8: {}", name, error),
+        )
+    })
+}
+
+/// # Safety
+/// `words` must be NULL or point to `len` NUL-terminated strings that outlive `'a`.
+unsafe fn to_words<'a>(words: *const *const c_char, len: usize) -> Result<Vec<&'a str>, ApiError> {
+    if len == 0 {
+        return Ok(vec![]);
+    }
+    check_not_null(words, "words")?;
+    slice::from_raw_parts(words, len)
+        .iter()
+        .map(|word| to_str(*word, "words"))
+        .collect()
+}
+
+fn to_token_kind(kind: TokenKind) -> Nlpo3TokenKind {
+    match kind {
+        TokenKind::Dictionary => Nlpo3TokenKind::Dictionary,
+        TokenKind::NonThai => Nlpo3TokenKind::NonThai,
+        TokenKind::Unknown => Nlpo3TokenKind::Unknown,
+    }
+}
+
+fn create(out: *mut *mut Nlpo3Tokenizer, tokenizer: NewmmTokenizer) {
+    let handle = Box::new(Nlpo3Tokenizer {
+        tokenizer: RwLock::new(tokenizer),
+    });
+    // out is checked by the caller
+    unsafe { *out = Box::into_raw(handle) };
+}
+
+/// Create a tokenizer using a dictionary file, one word per line.
+///
+/// # Safety
+/// `path` must be a NUL-terminated string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_new_from_path(
+    path: *const c_char,
+    out: *mut *mut Nlpo3Tokenizer,
+) -> Nlpo3Status {
+    run(|| {
+        check_not_null(out, "out")?;
+        let path = to_str(path, "path")?;
+        let tokenizer = NewmmTokenizer::from_dict_source(DictSource::FilePath(path.into()))
+            .map_err(|error| match error.downcast_ref::<io::Error>() {
+                Some(io_error) if io_error.kind() != io::ErrorKind::InvalidData => ApiError::new(
+                    Nlpo3Status::Io,
+                    format!("Cannot read dictionary file {}: {}", path, io_error),
+                ),
+                _ => ApiError::new(
+                    Nlpo3Status::InvalidDictionary,
+                    format!("Invalid dictionary file {}: {:#}", path, error),
+                ),
+            })?;
+        create(out, tokenizer);
+        Ok(())
+    })
+}
+
+/// Create a tokenizer using an array of `len` words.
+///
+/// # Safety
+/// `words` must point to `len` NUL-terminated strings
+/// (or may be NULL if `len` is 0), and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_new_from_words(
+    words: *const *const c_char,
+    len: usize,
+    out: *mut *mut Nlpo3Tokenizer,
+) -> Nlpo3Status {
+    run(|| {
+        check_not_null(out, "out")?;
+        let words = to_words(words, len)?;
+        let words: Vec<String> = words.into_iter().map(|word| word.to_string()).collect();
+        create(out, NewmmTokenizer::from_word_list(words));
+        Ok(())
+    })
+}
+
+/// Free a tokenizer. Does nothing if `tokenizer` is NULL.
+///
+/// # Safety
+/// `tokenizer` must be NULL or created by this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_free(tokenizer: *mut Nlpo3Tokenizer) {
+    if !tokenizer.is_null() {
+        drop(Box::from_raw(tokenizer));
+    }
+}
+
+/// Segment `text` into tokens, with byte offsets into `text`.
+/// On success, `out` must be freed with nlpo3_token_array_free().
+///
+/// # Safety
+/// `tokenizer` must be a valid tokenizer, `text` a NUL-terminated string,
+/// and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_segment(
+    tokenizer: *const Nlpo3Tokenizer,
+    text: *const c_char,
+    safe: bool,
+    parallel: bool,
+    out: *mut Nlpo3TokenArray,
+) -> Nlpo3Status {
+    run(|| {
+        check_not_null(tokenizer, "tokenizer")?;
+        check_not_null(out, "out")?;
+        let text = to_str(text, "text")?;
+        let tokenizer = (*tokenizer)
+            .tokenizer
+            .read()
+            .map_err(|_| ApiError::new(Nlpo3Status::Panic, "Tokenizer is unusable.".into()))?;
+        let tokens = tokenizer
+            .segment_to_byte_spans(text, safe, parallel)
+            .map_err(|error| ApiError::new(Nlpo3Status::Segment, error.to_string()))?;
+        let tokens: Box<[Nlpo3Token]> = tokens
+            .into_iter()
+            .map(|(token, span)| Nlpo3Token {
+                // the text has no NUL byte, so neither has a token
+                text: CString::new(token.text).unwrap_or_default().into_raw(),
+                start: span.start,
+                end: span.end,
+                kind: to_token_kind(token.kind),
+            })
+            .collect();
+        let len = tokens.len();
+        *out = Nlpo3TokenArray {
+            tokens: Box::into_raw(tokens) as *mut Nlpo3Token,
+            len,
+        };
+        Ok(())
+    })
+}
+
+/// Free the tokens of a token array, and set it to an empty array.
+/// Does nothing if `array` is NULL.
+///
+/// # Safety
+/// `array` must be NULL or filled by nlpo3_tokenizer_segment() and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_token_array_free(array: *mut Nlpo3TokenArray) {
+    if array.is_null() || (*array).tokens.is_null() {
+        return;
+    }
+    let tokens = Box::from_raw(ptr::slice_from_raw_parts_mut((*array).tokens, (*array).len));
+    for token in tokens.iter() {
+        drop(CString::from_raw(token.text));
+    }
+    (*array).tokens = ptr::null_mut();
+    (*array).len = 0;
+}
+
+fn change_words(
+    tokenizer: *mut Nlpo3Tokenizer,
+    words: *const *const c_char,
+    len: usize,
+    change: fn(&mut NewmmTokenizer, &[&str]),
+) -> Nlpo3Status {
+    run(|| {
+        check_not_null(tokenizer, "tokenizer")?;
+        // pointers are checked, validity is guaranteed by the caller
+        let words = unsafe { to_words(words, len)? };
+        let mut tokenizer = unsafe { &*tokenizer }
+            .tokenizer
+            .write()
+            .map_err(|_| ApiError::new(Nlpo3Status::Panic, "Tokenizer is unusable.".into()))?;
+        change(&mut tokenizer, &words);
+        Ok(())
+    })
+}
+
+/// Add an array of `len` words to the dictionary.
+///
+/// # Safety
+/// `tokenizer` must be a valid tokenizer, and `words` must point to
+/// `len` NUL-terminated strings (or may be NULL if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_add_word(
+    tokenizer: *mut Nlpo3Tokenizer,
+    words: *const *const c_char,
+    len: usize,
+) -> Nlpo3Status {
+    change_words(tokenizer, words, len, NewmmTokenizer::add_word)
+}
+
+/// Remove an array of `len` words from the dictionary.
+///
+/// # Safety
+/// `tokenizer` must be a valid tokenizer, and `words` must point to
+/// `len` NUL-terminated strings (or may be NULL if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn nlpo3_tokenizer_remove_word(
+    tokenizer: *mut Nlpo3Tokenizer,
+    words: *const *const c_char,
+    len: usize,
+) -> Nlpo3Status {
+    change_words(tokenizer, words, len, NewmmTokenizer::remove_word)
+}
+
+/// Message of the last failed call on this thread, or NULL if none.
+/// The message is valid until the next failed call on this thread.
+#[no_mangle]
+pub extern "C" fn nlpo3_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last_error| match &*last_error.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+#[cfg(test)]
+fn segment_texts(tokenizer: *const Nlpo3Tokenizer, text: &str) -> Vec<(String, usize, usize)> {
+    let text = CString::new(text).unwrap();
+    let mut array = Nlpo3TokenArray {
+        tokens: ptr::null_mut(),
+        len: 0,
+    };
+    let status =
+        unsafe { nlpo3_tokenizer_segment(tokenizer, text.as_ptr(), false, false, &mut array) };
+    assert_eq!(status, Nlpo3Status::Ok);
+    let result = unsafe { slice::from_raw_parts(array.tokens, array.len) }
+        .iter()
+        .map(|token| {
+            let token_text = unsafe { CStr::from_ptr(token.text) };
+            (
+                token_text.to_str().unwrap().to_string(),
+                token.start,
+                token.end,
+            )
+        })
+        .collect();
+    unsafe { nlpo3_token_array_free(&mut array) };
+    assert!(array.tokens.is_null());
+    result
+}
+
+#[test]
+fn test_segment_with_byte_offsets() {
+    let words = [CString::new("กิน").unwrap(), CString::new("ข้าว").unwrap()];
+    let word_pointers: Vec<*const c_char> = words.iter().map(|word| word.as_ptr()).collect();
+    let mut tokenizer: *mut Nlpo3Tokenizer = ptr::null_mut();
+    let status =
+        unsafe { nlpo3_tokenizer_new_from_words(word_pointers.as_ptr(), 2, &mut tokenizer) };
+    assert_eq!(status, Nlpo3Status::Ok);
+
+    assert_eq!(
+        segment_texts(tokenizer, "กินข้าว a"),
+        [
+            ("กิน".to_string(), 0, 9),
+            ("ข้าว".to_string(), 9, 21),
+            (" ".to_string(), 21, 22),
+            ("a".to_string(), 22, 23)
+        ]
+    );
+    assert!(segment_texts(tokenizer, "").is_empty());
+
+    let new_word = [CString::new("กินข้าว").unwrap()];
+    let new_word_pointers = [new_word[0].as_ptr()];
+    let status = unsafe { nlpo3_tokenizer_add_word(tokenizer, new_word_pointers.as_ptr(), 1) };
+    assert_eq!(status, Nlpo3Status::Ok);
+    assert_eq!(segment_texts(tokenizer, "กินข้าว").len(), 1);
+    let status = unsafe { nlpo3_tokenizer_remove_word(tokenizer, new_word_pointers.as_ptr(), 1) };
+    assert_eq!(status, Nlpo3Status::Ok);
+    assert_eq!(segment_texts(tokenizer, "กินข้าว").len(), 2);
+
+    unsafe { nlpo3_tokenizer_free(tokenizer) };
+}
+
+#[test]
+fn test_errors() {
+    let mut tokenizer: *mut Nlpo3Tokenizer = ptr::null_mut();
+    let path = CString::new("no/such/dict.txt").unwrap();
+    let status = unsafe { nlpo3_tokenizer_new_from_path(path.as_ptr(), &mut tokenizer) };
+    assert_eq!(status, Nlpo3Status::Io);
+    assert!(tokenizer.is_null());
+    let message = unsafe { CStr::from_ptr(nlpo3_last_error_message()) };
+    assert!(message
+        .to_str()
+        .unwrap()
+        .starts_with("Cannot read dictionary file no/such/dict.txt"));
+
+    let dir = CString::new(env!("CARGO_MANIFEST_DIR")).unwrap();
+    let status = unsafe { nlpo3_tokenizer_new_from_path(dir.as_ptr(), &mut tokenizer) };
+    assert_eq!(status, Nlpo3Status::Io);
+
+    let invalid_dict = std::env::temp_dir().join("nlpo3_c_invalid_dict.txt");
+    std::fs::write(&invalid_dict, [0xff_u8, b'\n']).unwrap();
+    let invalid_dict_path = CString::new(invalid_dict.to_str().unwrap()).unwrap();
+    let status =
+        unsafe { nlpo3_tokenizer_new_from_path(invalid_dict_path.as_ptr(), &mut tokenizer) };
+    std::fs::remove_file(&invalid_dict).unwrap();
+    assert_eq!(status, Nlpo3Status::InvalidDictionary);
+    assert!(tokenizer.is_null());
+
+    let status = unsafe { nlpo3_tokenizer_new_from_path(ptr::null(), &mut tokenizer) };
+    assert_eq!(status, Nlpo3Status::NullPointer);
+
+    let invalid = [0xff_u8, 0];
+    let invalid_words = [invalid.as_ptr() as *const c_char];
+    let status =
+        unsafe { nlpo3_tokenizer_new_from_words(invalid_words.as_ptr(), 1, &mut tokenizer) };
+    assert_eq!(status, Nlpo3Status::InvalidUtf8);
+}
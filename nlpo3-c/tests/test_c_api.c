@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Test the C API from C, compiled and run by tests/test_c_api.rs.
+ *
+ * Usage: test_c_api DICT_PATH
+ * where the dictionary contains "ปฏิบัติ" and "ปฏิวัติ".
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "nlpo3.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                               \
+    do {                                                               \
+        if (!(condition)) {                                            \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,     \
+                    __LINE__, #condition);                             \
+            failures++;                                                \
+        }                                                              \
+    } while (0)
+
+static void test_from_path(const char *dict_path) {
+    Nlpo3Tokenizer *tokenizer = NULL;
+    CHECK(nlpo3_tokenizer_new_from_path(dict_path, &tokenizer) == NLPO3_STATUS_OK);
+
+    Nlpo3TokenArray tokens;
+    CHECK(nlpo3_tokenizer_segment(tokenizer, "ปฏิบัติปฏิวัติ 1", false, false, &tokens) ==
+          NLPO3_STATUS_OK);
+    CHECK(tokens.len == 4);
+    CHECK(strcmp(tokens.tokens[0].text, "ปฏิบัติ") == 0);
+    CHECK(tokens.tokens[0].start == 0 && tokens.tokens[0].end == 21);
+    CHECK(tokens.tokens[0].kind == NLPO3_TOKEN_KIND_DICTIONARY);
+    CHECK(strcmp(tokens.tokens[1].text, "ปฏิวัติ") == 0);
+    CHECK(tokens.tokens[1].start == 21 && tokens.tokens[1].end == 42);
+    CHECK(tokens.tokens[3].kind == NLPO3_TOKEN_KIND_NON_THAI);
+    nlpo3_token_array_free(&tokens);
+    CHECK(tokens.tokens == NULL && tokens.len == 0);
+
+    nlpo3_tokenizer_free(tokenizer);
+}
+
+static void test_from_words(void) {
+    const char *words[] = {"กิน", "ข้าว"};
+    Nlpo3Tokenizer *tokenizer = NULL;
+    CHECK(nlpo3_tokenizer_new_from_words(words, 2, &tokenizer) == NLPO3_STATUS_OK);
+
+    Nlpo3TokenArray tokens;
+    CHECK(nlpo3_tokenizer_segment(tokenizer, "กินข้าว", true, true, &tokens) ==
+          NLPO3_STATUS_OK);
+    CHECK(tokens.len == 2);
+    nlpo3_token_array_free(&tokens);
+
+    const char *new_words[] = {"กินข้าว"};
+    CHECK(nlpo3_tokenizer_add_word(tokenizer, new_words, 1) == NLPO3_STATUS_OK);
+    CHECK(nlpo3_tokenizer_segment(tokenizer, "กินข้าว", false, false, &tokens) ==
+          NLPO3_STATUS_OK);
+    CHECK(tokens.len == 1);
+    CHECK(tokens.tokens[0].kind == NLPO3_TOKEN_KIND_DICTIONARY);
+    nlpo3_token_array_free(&tokens);
+
+    CHECK(nlpo3_tokenizer_remove_word(tokenizer, new_words, 1) == NLPO3_STATUS_OK);
+    CHECK(nlpo3_tokenizer_segment(tokenizer, "กินข้าว", false, false, &tokens) ==
+          NLPO3_STATUS_OK);
+    CHECK(tokens.len == 2);
+    nlpo3_token_array_free(&tokens);
+
+    nlpo3_tokenizer_free(tokenizer);
+}
+
+static void test_errors(void) {
+    Nlpo3Tokenizer *tokenizer = NULL;
+    CHECK(nlpo3_tokenizer_new_from_path("no/such/dict.txt", &tokenizer) == NLPO3_STATUS_IO);
+    CHECK(tokenizer == NULL);
+    CHECK(nlpo3_last_error_message() != NULL);
+
+    Nlpo3TokenArray tokens;
+    CHECK(nlpo3_tokenizer_segment(NULL, "กิน", false, false, &tokens) ==
+          NLPO3_STATUS_NULL_POINTER);
+
+    const char *invalid_words[] = {"\xff"};
+    CHECK(nlpo3_tokenizer_new_from_words(invalid_words, 1, &tokenizer) ==
+          NLPO3_STATUS_INVALID_UTF8);
+
+    /* freeing NULL does nothing */
+    nlpo3_tokenizer_free(NULL);
+    nlpo3_token_array_free(NULL);
+}
+
+int main(int argc, char *argv[]) {
+    if (argc != 2) {
+        fprintf(stderr, "Usage: %s DICT_PATH\n", argv[0]);
+        return 2;
+    }
+    test_from_path(argv[1]);
+    test_from_words();
+    test_errors();
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("All checks passed\n");
+    return 0;
+}
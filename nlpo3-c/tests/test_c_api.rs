@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Test the checked-in header and the C API from a C program.
+ *
+ * To regenerate include/nlpo3.h after changing the API, run:
+ * NLPO3_UPDATE_HEADER=1 cargo test --test c_api
+ */
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER_PATH: &str = "include/nlpo3.h";
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+// Directory of the built libraries,
+// the parent of the deps/ directory of this test executable.
+fn library_dir() -> PathBuf {
+    let test_exe = env::current_exe().unwrap();
+    test_exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+fn generate_header() -> String {
+    let config = cbindgen::Config::from_file(manifest_dir().join("cbindgen.toml")).unwrap();
+    let mut header: Vec<u8> = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(manifest_dir())
+        .with_config(config)
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    String::from_utf8(header).unwrap()
+}
+
+#[test]
+fn test_header_is_up_to_date() {
+    let generated = generate_header();
+    let header_path = manifest_dir().join(HEADER_PATH);
+    if env::var_os("NLPO3_UPDATE_HEADER").is_some() {
+        fs::write(&header_path, &generated).unwrap();
+    }
+    let checked_in = fs::read_to_string(&header_path).unwrap();
+    assert!(
+        checked_in == generated,
+        "{} is out of date, regenerate it with NLPO3_UPDATE_HEADER=1 cargo test --test c_api",
+        HEADER_PATH
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_c_program() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let program = out_dir.join("test_c_api");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(compiler)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest_dir().join("include"))
+        .arg(manifest_dir().join("tests/test_c_api.c"))
+        .arg(library_dir().join("libnlpo3_c.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "cannot compile tests/test_c_api.c");
+
+    let dict_path = manifest_dir().join("../tests/data/dict_with_empty_line.txt");
+    let output = Command::new(&program).arg(dict_path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "tests/test_c_api.c failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Tokenizer from a dictionary file or from a configuration file.
+ *
+ * A configuration file is TOML if its name ends with ".toml", JSON otherwise.
+ * A relative dictionary path in it is relative to the configuration file.
+*/
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result as AnyResult};
+use clap::Clap;
+use nlpo3::tokenizer::config::NewmmConfig;
+use nlpo3::tokenizer::DictSource;
+
+#[derive(Clap, Debug)]
+pub struct DictOpts {
+    /// Path to a dictionary file, one word per line
+    #[clap(short = 'd', long, required_unless_present = "config")]
+    dict_path: Option<PathBuf>,
+
+    /// Path to a tokenizer configuration file (TOML or JSON), instead of a dictionary file
+    #[clap(short = 'c', long, conflicts_with = "dict-path")]
+    config: Option<PathBuf>,
+}
+
+impl DictOpts {
+    /// Tokenizer configuration, from the configuration file
+    /// or with the dictionary file and default options.
+    pub fn load(&self) -> AnyResult<NewmmConfig> {
+        match (&self.config, &self.dict_path) {
+            (Some(config_path), _) => read_config(config_path),
+            (None, Some(dict_path)) => {
+                Ok(NewmmConfig::new(DictSource::FilePath(dict_path.clone())))
+            }
+            (None, None) => unreachable!("clap requires --dict-path or --config"),
+        }
+    }
+}
+
+fn parse_config(content: &str, toml: bool) -> AnyResult<NewmmConfig> {
+    Ok(if toml {
+        toml::from_str(content)?
+    } else {
+        serde_json::from_str(content)?
+    })
+}
+
+pub fn read_config(path: &Path) -> AnyResult<NewmmConfig> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read configuration file {}", path.display()))?;
+    let toml = path
+        .extension()
+        .is_some_and(|extension| extension == "toml");
+    let mut config = parse_config(&content, toml)
+        .with_context(|| format!("Invalid configuration file {}", path.display()))?;
+    if let DictSource::FilePath(dict_path) = &config.dictionary {
+        if let Some(config_dir) = path.parent() {
+            config.dictionary = DictSource::FilePath(config_dir.join(dict_path));
+        }
+    }
+    Ok(config)
+}
+
+#[test]
+fn test_parse_config() {
+    let toml_config = parse_config(
+        "safe = true\nadd_words = [\"กินข้าว\"]\n\n[dictionary]\nfile_path = \"words_th.txt\"\n",
+        true,
+    )
+    .unwrap();
+    let json_config = parse_config(
+        r#"{"safe": true, "add_words": ["กินข้าว"], "dictionary": {"file_path": "words_th.txt"}}"#,
+        false,
+    )
+    .unwrap();
+    assert_eq!(toml_config, json_config);
+    assert!(toml_config.safe);
+    assert!(!toml_config.parallel);
+    assert_eq!(
+        toml_config.dictionary,
+        DictSource::FilePath(PathBuf::from("words_th.txt"))
+    );
+    assert!(parse_config("[dictionary]\nfile_path = 1\n", true).is_err());
+}
@@ -0,0 +1,137 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Tokenize a corpus and count words.
+ *
+ * Each line of input is a document.
+*/
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+use anyhow::{Context, Result as AnyResult};
+use clap::{ArgEnum, Clap};
+use nlpo3::corpus::{top_k, CorpusStats};
+use nlpo3::tokenizer::newmm::NewmmTokenizer;
+
+use crate::config::DictOpts;
+
+#[derive(Clone, Copy, Debug, ArgEnum)]
+enum Table {
+    /// Word counts
+    Unigram,
+    /// Counts of pairs of adjacent words
+    Bigram,
+    /// Number of documents each word occurs in
+    Df,
+    /// Counts of out-of-dictionary words
+    Oov,
+}
+
+#[derive(Clap, Debug)]
+pub struct CountOpts {
+    #[clap(flatten)]
+    dict: DictOpts,
+
+    /// Input files, one document per line. Read from standard input if none is given
+    files: Vec<String>,
+
+    /// Statistics to output
+    #[clap(short = 't', long, arg_enum, default_value = "unigram")]
+    table: Table,
+
+    /// Output only the k most frequent entries
+    #[clap(short = 'k', long)]
+    top: Option<usize>,
+
+    /// Output only entries counted at least this many times
+    #[clap(short = 'm', long, default_value = "1")]
+    min_count: usize,
+
+    /// Run in safe mode to avoid long running edge cases
+    #[clap(short = 'z', long)]
+    safe: bool,
+}
+
+fn count_lines<R: BufRead>(
+    reader: R,
+    tokenizer: &NewmmTokenizer,
+    safe: bool,
+    stats: &mut CorpusStats,
+) -> AnyResult<()> {
+    for line in reader.lines() {
+        stats.add_document(tokenizer, &line?, safe)?;
+    }
+    Ok(())
+}
+
+/// Format a table as tab-separated lines of entry and count.
+fn format_table(
+    stats: &CorpusStats,
+    table: Table,
+    top: Option<usize>,
+    min_count: usize,
+) -> Vec<String> {
+    match table {
+        Table::Unigram => top_k(&stats.unigrams, top, min_count)
+            .into_iter()
+            .map(|(word, count)| format!("{}\t{}", word, count))
+            .collect(),
+        Table::Bigram => top_k(&stats.bigrams, top, min_count)
+            .into_iter()
+            .map(|((first, second), count)| format!("{}\t{}\t{}", first, second, count))
+            .collect(),
+        Table::Df => top_k(&stats.document_frequencies, top, min_count)
+            .into_iter()
+            .map(|(word, count)| format!("{}\t{}", word, count))
+            .collect(),
+        Table::Oov => top_k(&stats.oov, top, min_count)
+            .into_iter()
+            .map(|(word, count)| format!("{}\t{}", word, count))
+            .collect(),
+    }
+}
+
+pub fn run(opts: CountOpts) -> AnyResult<()> {
+    let config = opts.dict.load()?;
+    let tokenizer = config.build()?;
+    let safe = opts.safe || config.safe;
+    let mut stats = CorpusStats::new();
+    if opts.files.is_empty() {
+        count_lines(io::stdin().lock(), &tokenizer, safe, &mut stats)?;
+    }
+    for file in &opts.files {
+        let reader =
+            BufReader::new(File::open(file).with_context(|| format!("Cannot open file {}", file))?);
+        count_lines(reader, &tokenizer, safe, &mut stats)
+            .with_context(|| format!("Cannot count words in file {}", file))?;
+    }
+    eprintln!("documents: {}, tokens: {}", stats.documents, stats.tokens);
+    for line in format_table(&stats, opts.table, opts.top, opts.min_count) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_format_table() {
+    let tokenizer = NewmmTokenizer::from_word_list(vec!["กิน".to_string(), "ข้าว".to_string()]);
+    let mut stats = CorpusStats::new();
+    count_lines(
+        "กินข้าว\nกินกิน\nกินเกาเหลา\n".as_bytes(),
+        &tokenizer,
+        false,
+        &mut stats,
+    )
+    .unwrap();
+    assert_eq!(
+        format_table(&stats, Table::Unigram, Some(2), 1),
+        ["กิน\t4", "ข้าว\t1"]
+    );
+    assert_eq!(
+        format_table(&stats, Table::Bigram, None, 1),
+        ["กิน\tกิน\t1", "กิน\tข้าว\t1", "กิน\tเกาเหลา\t1"]
+    );
+    assert_eq!(format_table(&stats, Table::Df, None, 2), ["กิน\t3"]);
+    assert_eq!(format_table(&stats, Table::Oov, None, 1), ["เกาเหลา\t1"]);
+}
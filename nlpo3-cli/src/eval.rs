@@ -0,0 +1,103 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Evaluate segmentation against gold-segmented files
+ * (BEST-2010 style, words delimited by "|").
+*/
+use std::fs;
+
+use anyhow::{Context, Result as AnyResult};
+use clap::Clap;
+use nlpo3::eval::{evaluate_spans, ErrorKind, EvalResult};
+
+use crate::config::DictOpts;
+
+#[derive(Clap, Debug)]
+pub struct EvalOpts {
+    #[clap(flatten)]
+    dict: DictOpts,
+
+    /// Gold-segmented files, one sentence per line, words delimited by "|"
+    #[clap(required = true)]
+    files: Vec<String>,
+
+    /// Number of most frequent error patterns to show
+    #[clap(short = 'n', long, default_value = "20")]
+    top: usize,
+
+    /// Run in safe mode to avoid long running edge cases
+    #[clap(short = 'z', long)]
+    safe: bool,
+}
+
+const HEADER: &str =
+    "file\ttexts\tchar_precision\tchar_recall\tchar_f1\tword_precision\tword_recall\tword_f1";
+
+fn format_row(name: &str, result: &EvalResult) -> String {
+    format!(
+        "{}\t{}\t{:.4}\t{:.4}\t{:.4}\t{:.4}\t{:.4}\t{:.4}",
+        name,
+        result.texts,
+        result.char_boundary.precision(),
+        result.char_boundary.recall(),
+        result.char_boundary.f1(),
+        result.word.precision(),
+        result.word.recall(),
+        result.word.f1()
+    )
+}
+
+fn format_errors(result: &EvalResult, top: usize) -> String {
+    let mut lines = vec!["count\tkind\tgold\tpredicted".to_string()];
+    for (pattern, count) in result.top_errors(top) {
+        let kind = match pattern.kind {
+            ErrorKind::OverSegmentation => "over",
+            ErrorKind::UnderSegmentation => "under",
+            ErrorKind::Mixed => "mixed",
+        };
+        lines.push(format!(
+            "{}\t{}\t{}\t{}",
+            count, kind, pattern.gold, pattern.predicted
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn run(opts: EvalOpts) -> AnyResult<()> {
+    let config = opts.dict.load()?;
+    let tokenizer = config.build()?;
+    let safe = opts.safe || config.safe;
+    let mut total = EvalResult::default();
+    println!("{}", HEADER);
+    for file in &opts.files {
+        let content =
+            fs::read_to_string(file).with_context(|| format!("Cannot read file {}", file))?;
+        let result = evaluate_spans(&tokenizer, content.lines(), safe)
+            .with_context(|| format!("Cannot evaluate file {}", file))?;
+        println!("{}", format_row(file, &result));
+        total.merge(&result);
+    }
+    println!("{}", format_row("total", &total));
+    if opts.top > 0 {
+        println!();
+        println!("{}", format_errors(&total, opts.top));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_format() {
+    use nlpo3::tokenizer::newmm::NewmmTokenizer;
+
+    let tokenizer = NewmmTokenizer::from_word_list(vec!["ข้าว".to_string(), "ผัด".to_string()]);
+    let result = evaluate_spans(&tokenizer, vec!["ข้าวผัด|", "ข้าว|ผัด|"], false).unwrap();
+    assert_eq!(
+        format_row("total", &result),
+        "total\t2\t0.5000\t1.0000\t0.6667\t0.5000\t0.6667\t0.5714"
+    );
+    assert_eq!(
+        format_errors(&result, 5),
+        "count\tkind\tgold\tpredicted\n1\tover\tข้าวผัด\tข้าว|ผัด"
+    );
+}
@@ -1,8 +1,14 @@
 // SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 // SPDX-License-Identifier: Apache-2.0
 
+mod config;
+mod count;
+mod eval;
+mod repl;
+mod serve;
+
 use clap::Clap;
-use nlpo3::tokenizer::newmm_custom::Newmm;
+use config::DictOpts;
 use nlpo3::tokenizer::tokenizer_trait::Tokenizer;
 use std::io;
 use std::io::BufRead;
@@ -17,14 +23,21 @@ struct App {
 #[derive(Clap, Debug)]
 enum SubCommand {
     /// Tokenize a string into words.
-    #[clap()]
     Segment(SegmentOpts),
+    /// Run a local HTTP/JSON tokenization server.
+    Serve(serve::ServeOpts),
+    /// Debug a dictionary interactively.
+    Repl(repl::ReplOpts),
+    /// Evaluate segmentation against gold-segmented files.
+    Eval(eval::EvalOpts),
+    /// Tokenize a corpus and count words.
+    Count(count::CountOpts),
 }
 
 #[derive(Clap, Debug)]
 struct SegmentOpts {
-    #[clap(short = 'd', long, default_value = "default")]
-    dict_path: String,
+    #[clap(flatten)]
+    dict: DictOpts,
 
     #[clap(short = 's', long, default_value = "|")]
     word_delimiter: String,
@@ -38,26 +51,31 @@ struct SegmentOpts {
     parallel: bool,
 }
 
-fn main() {
-    let opt = App::parse();
-
-    let SubCommand::Segment(segment_opts) = opt.subcommand;
-    let dict_path = match segment_opts.dict_path.as_str() {
-        "default" => None,
-        dict_name => Some(dict_name),
-    };
-
-    let newmm = Newmm::new(dict_path);
+fn segment(segment_opts: SegmentOpts) -> anyhow::Result<()> {
+    let config = segment_opts.dict.load()?;
+    let newmm = config.build()?;
+    let safe = segment_opts.safe || config.safe;
+    let parallel = segment_opts.parallel || config.parallel;
     for line_opt in io::stdin().lock().lines() {
         let cleaned_line = match line_opt {
             Ok(line) => line.trim_end_matches('\n').to_string(),
             Err(e) => panic!("Cannot read line {}", e),
         };
-        let toks = newmm.segment(
-            &cleaned_line,
-            Some(segment_opts.safe),
-            Some(segment_opts.parallel),
-        );
+        let toks = newmm.segment_to_string(&cleaned_line, safe, parallel);
         println!("{}", toks.join(segment_opts.word_delimiter.as_str()));
     }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let opt = App::parse();
+
+    match opt.subcommand {
+        SubCommand::Segment(segment_opts) => segment(segment_opts)?,
+        SubCommand::Serve(serve_opts) => serve::run(serve_opts)?,
+        SubCommand::Repl(repl_opts) => repl::run(repl_opts)?,
+        SubCommand::Eval(eval_opts) => eval::run(eval_opts)?,
+        SubCommand::Count(count_opts) => count::run(count_opts)?,
+    }
+    Ok(())
 }
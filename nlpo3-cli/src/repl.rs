@@ -0,0 +1,198 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Interactive read-eval-print loop (REPL) for dictionary debugging.
+ *
+ * For each input line, shows the tokens, the Thai Character Cluster (TCC)
+ * boundaries, the candidate word graph, and the tokens that went through
+ * the unknown-word path.
+*/
+use std::io::{self, BufRead, Write};
+
+use anyhow::Result as AnyResult;
+use clap::Clap;
+use nlpo3::tokenizer::newmm::{NewmmTokenizer, SegmentTrace};
+use nlpo3::tokenizer::token::TokenKind;
+
+use crate::config::DictOpts;
+
+const HELP: &str = "\
+Type a text to segment it, or one of these commands:
+  :add WORD...     add words to the dictionary and re-run the last text
+  :remove WORD...  remove words from the dictionary and re-run the last text
+  :again           re-run the last text
+  :help            show this help
+  :quit            exit";
+
+#[derive(Clap, Debug)]
+pub struct ReplOpts {
+    #[clap(flatten)]
+    dict: DictOpts,
+}
+
+pub struct Repl {
+    tokenizer: NewmmTokenizer,
+    last_text: Option<String>,
+}
+
+/// Join the parts of `text` between `boundaries` (character indices) with "|".
+fn join_at(text: &str, boundaries: &[usize]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut start = 0;
+    let mut parts: Vec<String> = Vec::with_capacity(boundaries.len());
+    for &end in boundaries {
+        parts.push(chars[start..end].iter().collect());
+        start = end;
+    }
+    parts.join("|")
+}
+
+fn format_trace(text: &str, trace: &SegmentTrace) -> String {
+    let token_texts: Vec<&str> = trace
+        .tokens
+        .iter()
+        .map(|token| token.text.as_str())
+        .collect();
+
+    let mut edges: Vec<String> = Vec::new();
+    for (begin, end) in &trace.graph {
+        match edges.last_mut() {
+            Some(last) if last.starts_with(&format!("{} ->", begin)) => {
+                last.push_str(&format!(", {}", end));
+            }
+            _ => edges.push(format!("{} -> {}", begin, end)),
+        }
+    }
+
+    let unknown: Vec<String> = trace
+        .tokens
+        .iter()
+        .filter(|token| token.kind != TokenKind::Dictionary)
+        .map(|token| {
+            let kind = match token.kind {
+                TokenKind::NonThai => "non-Thai",
+                _ => "unknown",
+            };
+            format!("[{}, {}) {:?} {}", token.start, token.end, token.text, kind)
+        })
+        .collect();
+
+    format!(
+        "tokens:  {}\ntcc:     {}\ngraph:   {}\nunknown: {}",
+        token_texts.join("|"),
+        join_at(text, &trace.tcc_boundaries),
+        edges.join("; "),
+        if unknown.is_empty() {
+            "-".to_string()
+        } else {
+            unknown.join("; ")
+        }
+    )
+}
+
+impl Repl {
+    pub fn new(tokenizer: NewmmTokenizer) -> Self {
+        Self {
+            tokenizer,
+            last_text: None,
+        }
+    }
+
+    fn run_last(&self) -> String {
+        match &self.last_text {
+            Some(text) => match self.tokenizer.trace(text) {
+                Ok(trace) => format_trace(text, &trace),
+                Err(error) => format!("error: {}", error),
+            },
+            None => "No text to re-run.".to_string(),
+        }
+    }
+
+    /// Execute one input line and return the output to show,
+    /// or `None` when the user asks to quit.
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let line = line.trim_end_matches(&['\r', '\n'][..]);
+        let (command, args) = match line.split_once(' ') {
+            Some((command, args)) => (command, args.split_whitespace().collect()),
+            None => (line, vec![]),
+        };
+        let output = match command {
+            ":quit" | ":q" => return None,
+            ":help" => HELP.to_string(),
+            ":again" => self.run_last(),
+            ":add" | ":remove" if args.is_empty() => format!("Usage: {} WORD...", command),
+            ":add" => {
+                self.tokenizer.add_word(&args);
+                format!("Added {} word(s).\n{}", args.len(), self.run_last())
+            }
+            ":remove" => {
+                self.tokenizer.remove_word(&args);
+                format!("Removed {} word(s).\n{}", args.len(), self.run_last())
+            }
+            _ if command.starts_with(':') => {
+                format!("Unknown command {}. Type :help for help.", command)
+            }
+            _ => {
+                self.last_text = Some(line.to_string());
+                self.run_last()
+            }
+        };
+        Some(output)
+    }
+}
+
+pub fn run(opts: ReplOpts) -> AnyResult<()> {
+    let mut repl = Repl::new(opts.dict.load()?.build()?);
+    println!("{}", HELP);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        match repl.execute(&line) {
+            Some(output) => println!("{}", output),
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_repl_segment() {
+    let mut repl = Repl::new(NewmmTokenizer::from_word_list(vec![
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+    ]));
+    assert_eq!(
+        repl.execute("กินข้าวabcสวย").unwrap(),
+        "tokens:  กิน|ข้าว|abc|สวย\n\
+         tcc:     กิ|น|ข้า|ว|a|b|c|ส|ว|ย\n\
+         graph:   0 -> 3; 3 -> 7; 7 -> 10; 10 -> 13\n\
+         unknown: [7, 10) \"abc\" non-Thai; [10, 13) \"สวย\" unknown"
+    );
+    assert!(repl.execute(":quit").is_none());
+}
+
+#[test]
+fn test_repl_add_and_remove_word() {
+    let mut repl = Repl::new(NewmmTokenizer::from_word_list(vec![
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+    ]));
+    assert_eq!(repl.execute(":again").unwrap(), "No text to re-run.");
+    repl.execute("กินข้าว");
+    let added = repl.execute(":add กินข้าว").unwrap();
+    assert!(added.starts_with("Added 1 word(s).\ntokens:  กินข้าว\n"));
+    let removed = repl.execute(":remove กินข้าว").unwrap();
+    assert!(removed.starts_with("Removed 1 word(s).\ntokens:  กิน|ข้าว\n"));
+    assert_eq!(repl.execute(":add").unwrap(), "Usage: :add WORD...");
+    assert!(repl
+        .execute(":oops")
+        .unwrap()
+        .starts_with("Unknown command"));
+}
@@ -0,0 +1,490 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Local HTTP/JSON tokenization server.
+ *
+ * Endpoints:
+ *  * GET  /health         -> {"status": "ok"}
+ *  * GET  /dicts          -> {"dicts": [...], "default": "..."}
+ *  * POST /segment        <- {"text": "...", "dict": "...", "safe": false, "parallel": false}
+ *  * POST /segment/batch  <- {"texts": ["..."], "dict": "...", "safe": false, "parallel": false}
+ *
+ * "dict", "safe" and "parallel" are optional.
+ * Without "dict", the first loaded dictionary is used.
+ * "safe" and "parallel" are also turned on by the configuration file
+ * of the dictionary, as with the segment command.
+*/
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::{anyhow, Context, Result as AnyResult};
+use clap::Clap;
+use nlpo3::tokenizer::config::NewmmConfig;
+use nlpo3::tokenizer::newmm::NewmmTokenizer;
+use nlpo3::tokenizer::tokenizer_trait::Tokenizer;
+use nlpo3::tokenizer::DictSource;
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::config::read_config;
+
+const DEFAULT_DICT_NAME: &str = "default";
+
+#[derive(Clap, Debug)]
+pub struct ServeOpts {
+    /// Dictionary to load, as NAME=PATH or PATH (named "default").
+    /// Can be repeated; the first one is used when a request names no dictionary
+    #[clap(short = 'd', long = "dict", required_unless_present = "configs")]
+    dicts: Vec<String>,
+
+    /// Tokenizer configuration file to load, as NAME=PATH or PATH (named "default"),
+    /// in the format of the --config option of segment.
+    /// Can be repeated; loaded after the dictionaries of --dict
+    #[clap(short = 'c', long = "config")]
+    configs: Vec<String>,
+
+    /// Address to listen on
+    #[clap(short = 'b', long, default_value = "127.0.0.1:8080")]
+    bind: String,
+
+    /// Maximum request body size, in bytes
+    #[clap(long, default_value = "1048576")]
+    max_body_bytes: usize,
+
+    /// Maximum number of texts in a batch request
+    #[clap(long, default_value = "1000")]
+    max_batch_size: usize,
+
+    /// Number of worker threads handling requests
+    #[clap(short = 'w', long, default_value = "4")]
+    workers: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub max_body_bytes: usize,
+    pub max_batch_size: usize,
+}
+
+/// A tokenizer with the segmentation options of its configuration.
+pub struct ConfiguredTokenizer {
+    tokenizer: NewmmTokenizer,
+    safe: bool,
+    parallel: bool,
+}
+
+/// Named tokenizers kept in memory for the lifetime of the server.
+pub struct TokenizerCollection {
+    tokenizers: BTreeMap<String, ConfiguredTokenizer>,
+    default_name: String,
+}
+
+impl TokenizerCollection {
+    /// Create a collection from (name, configuration) pairs.
+    /// The first pair becomes the default tokenizer.
+    pub fn new(named_configs: Vec<(String, NewmmConfig)>) -> AnyResult<Self> {
+        let default_name = match named_configs.first() {
+            Some((name, _)) => name.clone(),
+            None => return Err(anyhow!("At least one dictionary is required")),
+        };
+        let mut tokenizers = BTreeMap::new();
+        for (name, config) in named_configs {
+            let tokenizer = ConfiguredTokenizer {
+                tokenizer: config
+                    .build()
+                    .with_context(|| format!("Cannot load dictionary {}", name))?,
+                safe: config.safe,
+                parallel: config.parallel,
+            };
+            if tokenizers.insert(name.clone(), tokenizer).is_some() {
+                return Err(anyhow!("Dictionary name {} is used more than once", name));
+            }
+        }
+        Ok(Self {
+            tokenizers,
+            default_name,
+        })
+    }
+
+    fn get(&self, name: Option<&str>) -> Option<&ConfiguredTokenizer> {
+        self.tokenizers
+            .get(name.unwrap_or(self.default_name.as_str()))
+    }
+}
+
+#[derive(Deserialize)]
+struct SegmentRequest {
+    text: String,
+    #[serde(default)]
+    dict: Option<String>,
+    #[serde(default)]
+    safe: bool,
+    #[serde(default)]
+    parallel: bool,
+}
+
+#[derive(Deserialize)]
+struct BatchSegmentRequest {
+    texts: Vec<String>,
+    #[serde(default)]
+    dict: Option<String>,
+    #[serde(default)]
+    safe: bool,
+    #[serde(default)]
+    parallel: bool,
+}
+
+#[derive(Serialize)]
+struct SegmentResponse {
+    tokens: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct BatchSegmentResponse {
+    tokens: Vec<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct DictsResponse<'a> {
+    dicts: Vec<&'a str>,
+    default: &'a str,
+}
+
+#[derive(Serialize)]
+struct StatusResponse {
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Status code and JSON body of a response.
+type Reply = (u16, String);
+
+fn json_reply<T: Serialize>(status: u16, value: &T) -> Reply {
+    (status, serde_json::to_string(value).unwrap())
+}
+
+fn error_reply(status: u16, message: String) -> Reply {
+    json_reply(status, &ErrorResponse { error: message })
+}
+
+pub struct Service {
+    collection: TokenizerCollection,
+    limits: Limits,
+}
+
+impl Service {
+    pub fn new(collection: TokenizerCollection, limits: Limits) -> Self {
+        Self { collection, limits }
+    }
+
+    /// Read the request body, respecting the body size limit.
+    fn read_body(&self, request: &mut Request) -> Result<Vec<u8>, Reply> {
+        let too_large = || {
+            error_reply(
+                413,
+                format!(
+                    "Request body is larger than {} bytes",
+                    self.limits.max_body_bytes
+                ),
+            )
+        };
+        if matches!(request.body_length(), Some(length) if length > self.limits.max_body_bytes) {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        request
+            .as_reader()
+            .take(self.limits.max_body_bytes as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|error| error_reply(400, format!("Cannot read request body: {}", error)))?;
+        if body.len() > self.limits.max_body_bytes {
+            return Err(too_large());
+        }
+        Ok(body)
+    }
+
+    fn tokenizer(&self, dict: Option<&str>) -> Result<&ConfiguredTokenizer, Reply> {
+        self.collection.get(dict).ok_or_else(|| {
+            error_reply(
+                404,
+                format!("Dictionary name {} does not exist.", dict.unwrap_or("")),
+            )
+        })
+    }
+
+    fn segment(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let request: SegmentRequest = serde_json::from_slice(body)
+            .map_err(|error| error_reply(400, format!("Invalid request: {}", error)))?;
+        let configured = self.tokenizer(request.dict.as_deref())?;
+        let tokens = configured
+            .tokenizer
+            .segment(
+                &request.text,
+                request.safe || configured.safe,
+                request.parallel || configured.parallel,
+            )
+            .map_err(|error| error_reply(500, error.to_string()))?;
+        Ok(json_reply(200, &SegmentResponse { tokens }))
+    }
+
+    fn segment_batch(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let request: BatchSegmentRequest = serde_json::from_slice(body)
+            .map_err(|error| error_reply(400, format!("Invalid request: {}", error)))?;
+        if request.texts.len() > self.limits.max_batch_size {
+            return Err(error_reply(
+                413,
+                format!("Batch has more than {} texts", self.limits.max_batch_size),
+            ));
+        }
+        let configured = self.tokenizer(request.dict.as_deref())?;
+        let safe = request.safe || configured.safe;
+        let parallel = request.parallel || configured.parallel;
+        let tokens = request
+            .texts
+            .iter()
+            .map(|text| configured.tokenizer.segment(text, safe, parallel))
+            .collect::<AnyResult<Vec<_>>>()
+            .map_err(|error| error_reply(500, error.to_string()))?;
+        Ok(json_reply(200, &BatchSegmentResponse { tokens }))
+    }
+
+    fn route(&self, request: &mut Request) -> Reply {
+        let path = request.url().split('?').next().unwrap_or("").to_string();
+        let result = match (request.method(), path.as_str()) {
+            (Method::Get, "/health") => Ok(json_reply(200, &StatusResponse { status: "ok" })),
+            (Method::Get, "/dicts") => Ok(json_reply(
+                200,
+                &DictsResponse {
+                    dicts: self
+                        .collection
+                        .tokenizers
+                        .keys()
+                        .map(String::as_str)
+                        .collect(),
+                    default: &self.collection.default_name,
+                },
+            )),
+            (Method::Post, "/segment") => {
+                self.read_body(request).and_then(|body| self.segment(&body))
+            }
+            (Method::Post, "/segment/batch") => self
+                .read_body(request)
+                .and_then(|body| self.segment_batch(&body)),
+            (_, "/health") | (_, "/dicts") | (_, "/segment") | (_, "/segment/batch") => {
+                Err(error_reply(405, "Method not allowed".to_string()))
+            }
+            _ => Err(error_reply(404, format!("Unknown endpoint {}", path))),
+        };
+        match result {
+            Ok(reply) | Err(reply) => reply,
+        }
+    }
+
+    pub fn handle(&self, mut request: Request) {
+        let (status, body) = self.route(&mut request);
+        let content_type =
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(content_type);
+        // The client may have gone away; there is nobody to report to.
+        let _ = request.respond(response);
+    }
+}
+
+/// Serve requests from `server` on `workers` threads until the server is closed.
+pub fn serve(server: Arc<Server>, service: Arc<Service>, workers: usize) {
+    let handles: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+            let server = server.clone();
+            let service = service.clone();
+            thread::spawn(move || {
+                while let Ok(request) = server.recv() {
+                    service.handle(request);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+/// Parse a NAME=PATH or PATH dictionary or configuration argument.
+fn parse_dict_arg(arg: &str) -> (String, String) {
+    match arg.split_once('=') {
+        Some((name, path)) => (name.to_string(), path.to_string()),
+        None => (DEFAULT_DICT_NAME.to_string(), arg.to_string()),
+    }
+}
+
+pub fn run(opts: ServeOpts) -> AnyResult<()> {
+    let mut named_configs: Vec<(String, NewmmConfig)> = opts
+        .dicts
+        .iter()
+        .map(|arg| {
+            let (name, path) = parse_dict_arg(arg);
+            eprintln!("Loading dictionary {} from {}", name, path);
+            let config = NewmmConfig::new(DictSource::FilePath(PathBuf::from(path)));
+            (name, config)
+        })
+        .collect();
+    for arg in opts.configs.iter() {
+        let (name, path) = parse_dict_arg(arg);
+        eprintln!("Loading dictionary {} with configuration {}", name, path);
+        named_configs.push((name, read_config(Path::new(&path))?));
+    }
+    let collection = TokenizerCollection::new(named_configs)?;
+    let limits = Limits {
+        max_body_bytes: opts.max_body_bytes,
+        max_batch_size: opts.max_batch_size,
+    };
+    let server = Server::http(opts.bind.as_str())
+        .map_err(|error| anyhow!("Cannot listen on {}: {}", opts.bind, error))?;
+    eprintln!("Listening on http://{}", server.server_addr());
+    serve(
+        Arc::new(server),
+        Arc::new(Service::new(collection, limits)),
+        opts.workers,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+fn start_test_server(limits: Limits) -> std::net::SocketAddr {
+    let mut food_config = NewmmConfig::new(DictSource::WordList(vec!["กินข้าว".to_string()]));
+    food_config.normalizer = Some(nlpo3::normalize::Normalizer::new());
+    let collection = TokenizerCollection::new(vec![
+        (
+            "default".to_string(),
+            NewmmConfig::new(DictSource::WordList(vec![
+                "กิน".to_string(),
+                "ข้าว".to_string(),
+            ])),
+        ),
+        ("food".to_string(), food_config),
+    ])
+    .unwrap();
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let service = Arc::new(Service::new(collection, limits));
+    thread::spawn(move || serve(Arc::new(server), service, 2));
+    addr
+}
+
+#[cfg(test)]
+fn request(addr: std::net::SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or_default();
+    (status, body)
+}
+
+#[cfg(test)]
+const TEST_LIMITS: Limits = Limits {
+    max_body_bytes: 256,
+    max_batch_size: 2,
+};
+
+#[test]
+fn test_health_and_dicts() {
+    let addr = start_test_server(TEST_LIMITS);
+    assert_eq!(
+        request(addr, "GET", "/health", ""),
+        (200, r#"{"status":"ok"}"#.to_string())
+    );
+    assert_eq!(
+        request(addr, "GET", "/dicts", ""),
+        (
+            200,
+            r#"{"dicts":["default","food"],"default":"default"}"#.to_string()
+        )
+    );
+    assert_eq!(request(addr, "POST", "/health", "").0, 405);
+    assert_eq!(request(addr, "GET", "/nothing", "").0, 404);
+}
+
+#[test]
+fn test_segment() {
+    let addr = start_test_server(TEST_LIMITS);
+    assert_eq!(
+        request(addr, "POST", "/segment", r#"{"text":"กินข้าว"}"#),
+        (200, r#"{"tokens":["กิน","ข้าว"]}"#.to_string())
+    );
+    assert_eq!(
+        request(
+            addr,
+            "POST",
+            "/segment",
+            r#"{"text":"กินข้าว","dict":"food","safe":true}"#
+        ),
+        (200, r#"{"tokens":["กินข้าว"]}"#.to_string())
+    );
+    // normalized by the configuration of the dictionary
+    assert_eq!(
+        request(
+            addr,
+            "POST",
+            "/segment",
+            r#"{"text":"กินข้าาาว","dict":"food"}"#
+        ),
+        (200, r#"{"tokens":["กินข้าว"]}"#.to_string())
+    );
+    assert_eq!(
+        request(addr, "POST", "/segment", r#"{"text":"กิน","dict":"none"}"#).0,
+        404
+    );
+    assert_eq!(request(addr, "POST", "/segment", r#"{"txt":"กิน"}"#).0, 400);
+}
+
+#[test]
+fn test_segment_batch() {
+    let addr = start_test_server(TEST_LIMITS);
+    assert_eq!(
+        request(addr, "POST", "/segment/batch", r#"{"texts":["กินข้าว",""]}"#),
+        (200, r#"{"tokens":[["กิน","ข้าว"],[]]}"#.to_string())
+    );
+    assert_eq!(
+        request(
+            addr,
+            "POST",
+            "/segment/batch",
+            r#"{"texts":["กิน","กิน","กิน"]}"#
+        )
+        .0,
+        413
+    );
+}
+
+#[test]
+fn test_body_size_limit() {
+    let addr = start_test_server(TEST_LIMITS);
+    let body = format!(r#"{{"text":"{}"}}"#, "ก".repeat(100));
+    assert_eq!(request(addr, "POST", "/segment", &body).0, 413);
+}
@@ -6,16 +6,188 @@ import * as nativeModule from './rust_mod'
  * Load dict from dictionary file and store in hash map with key = dictName for ***segment*** function to use.
  * 
  * filePath is an absolute path to the dictionary file.
+ * 
+ * Returns false if dictName already exists. Throws an Error if the file cannot be read.
  */
-export const loadDict = (filePath: string, dictName: string): string => {
+export const loadDict = (filePath: string, dictName: string): boolean => {
     return nativeModule.loadDict(filePath, dictName)
 }
 /**
  * Perform segmentation on "text" argument with words from dict "dictName".
  * 
- * Dictionary "dictName" must be loaded with **loadDict** function first.
+ * Dictionary "dictName" must be loaded with **loadDict** function first,
+ * otherwise an Error is thrown.
  * 
  */
 export const segment = (text: string, dictName: string, safe = false, parallel = false): string[] => {
     return nativeModule.segment(text, dictName, safe, parallel)
 }
+/**
+ * Minimal interface of an AbortSignal, used to cancel asynchronous segmentation.
+ */
+export interface CancelSignal {
+    readonly aborted: boolean
+    addEventListener(type: 'abort', listener: () => void): void
+    removeEventListener(type: 'abort', listener: () => void): void
+}
+/**
+ * Options of asynchronous segmentation.
+ */
+export interface SegmentAsyncOptions {
+    /** Use safe mode, defaults to false */
+    safe?: boolean
+    /** Cancel segmentation when this signal is aborted */
+    signal?: CancelSignal
+}
+/**
+ * Options of asynchronous batch segmentation.
+ */
+export interface SegmentBatchAsyncOptions extends SegmentAsyncOptions {
+    /** Maximum number of texts segmented at the same time, defaults to 4 */
+    concurrency?: number
+}
+
+/**
+ * Options of dictionary prefix search.
+ */
+export interface WordsWithPrefixOptions {
+    /** Maximum number of words, defaults to no limit */
+    limit?: number
+    /**
+     * 'alphabetical' for Unicode code point order, or 'frequency' for
+     * highest frequency first, words without a frequency last.
+     * Defaults to 'alphabetical'
+     */
+    order?: 'alphabetical' | 'frequency'
+}
+
+const abortError = (): Error => {
+    const error = new Error('Segmentation was cancelled.')
+    error.name = 'AbortError'
+    return error
+}
+/**
+ * Dictionary-based maximal matching word tokenizer.
+ * 
+ * Each object owns its dictionary, which is freed when the object is garbage collected.
+ */
+export class NewmmTokenizer {
+    private readonly handle: nativeModule.TokenizerHandle
+
+    /**
+     * Create a tokenizer using a dictionary file, one word per line,
+     * possibly compressed with gzip or zstd.
+     * 
+     * Throws an Error if the file cannot be read or its content is invalid.
+     */
+    constructor(dictPath: string)
+    /** @internal */
+    constructor(dictPath: string | nativeModule.TokenizerHandle)
+    constructor(dictPath: string | nativeModule.TokenizerHandle) {
+        this.handle = typeof dictPath === 'string' ? nativeModule.tokenizerNew(dictPath) : dictPath
+    }
+    /**
+     * Create a tokenizer using an array of words.
+     */
+    static fromWordList(words: string[]): NewmmTokenizer {
+        return new NewmmTokenizer(nativeModule.tokenizerFromWordList(words))
+    }
+    /**
+     * Create a tokenizer using the content of a dictionary file,
+     * possibly compressed with gzip or zstd, without a temporary file.
+     * 
+     * Throws an Error if the content is invalid.
+     */
+    static fromBytes(data: Buffer | Uint8Array): NewmmTokenizer {
+        return new NewmmTokenizer(nativeModule.tokenizerFromBytes(data))
+    }
+    /**
+     * Perform segmentation on "text" argument.
+     */
+    segment(text: string, safe = false, parallel = false): string[] {
+        return nativeModule.tokenizerSegment(this.handle, text, safe, parallel)
+    }
+    /**
+     * Perform segmentation on "text" argument on the libuv thread pool,
+     * without blocking the event loop.
+     * 
+     * The tokens are the same as those of segment(text, options.safe).
+     * If options.signal is aborted before segmentation is done,
+     * the Promise is rejected with an AbortError.
+     */
+    segmentAsync(text: string, options: SegmentAsyncOptions = {}): Promise<string[]> {
+        const {safe = false, signal} = options
+        if (signal?.aborted) {
+            return Promise.reject(abortError())
+        }
+        const cancelFlag = nativeModule.cancelFlagNew()
+        const onAbort = () => nativeModule.cancelFlagCancel(cancelFlag)
+        const cleanUp = () => signal?.removeEventListener('abort', onAbort)
+        signal?.addEventListener('abort', onAbort)
+        return nativeModule.tokenizerSegmentAsync(this.handle, text, safe, cancelFlag).then(
+            (tokens) => {
+                cleanUp()
+                return tokens
+            },
+            (error) => {
+                cleanUp()
+                throw error
+            })
+    }
+    /**
+     * Perform segmentation on each of "texts" on the libuv thread pool,
+     * with at most options.concurrency texts segmented at the same time.
+     * 
+     * If options.signal is aborted, texts not yet done are cancelled
+     * and the Promise is rejected with an AbortError.
+     */
+    async segmentBatchAsync(texts: string[], options: SegmentBatchAsyncOptions = {}): Promise<string[][]> {
+        const {concurrency = 4, ...segmentOptions} = options
+        if (!(concurrency >= 1)) {
+            throw new RangeError('concurrency must be at least 1')
+        }
+        const results: string[][] = new Array(texts.length)
+        let next = 0
+        const worker = async () => {
+            while (next < texts.length) {
+                const i = next++
+                results[i] = await this.segmentAsync(texts[i], segmentOptions)
+            }
+        }
+        const workers = Array.from({length: Math.min(concurrency, texts.length)}, worker)
+        await Promise.all(workers)
+        return results
+    }
+    /**
+     * Add words to the dictionary.
+     */
+    addWord(words: string[]): void {
+        nativeModule.tokenizerAddWord(this.handle, words)
+    }
+    /**
+     * Remove words from the dictionary.
+     */
+    removeWord(words: string[]): void {
+        nativeModule.tokenizerRemoveWord(this.handle, words)
+    }
+    /**
+     * Check whether a word is in the dictionary.
+     */
+    containsWord(word: string): boolean {
+        return nativeModule.tokenizerContainsWord(this.handle, word)
+    }
+    /**
+     * Find dictionary words starting with "prefix", for example to autocomplete
+     * a search box. An empty prefix matches every word.
+     */
+    wordsWithPrefix(prefix: string, options: WordsWithPrefixOptions = {}): string[] {
+        const {limit, order = 'alphabetical'} = options
+        return nativeModule.tokenizerWordsWithPrefix(this.handle, prefix, limit, order === 'frequency')
+    }
+    /**
+     * List all dictionary words, in Unicode code point order.
+     */
+    words(): string[] {
+        return nativeModule.tokenizerWords(this.handle)
+    }
+}
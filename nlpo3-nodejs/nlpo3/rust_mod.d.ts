@@ -1,6 +1,36 @@
 // SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 // SPDX-License-Identifier: Apache-2.0
 
+/** Opaque handle to a tokenizer owned by the native module */
+export interface TokenizerHandle {
+    readonly __brand: 'TokenizerHandle';
+}
+
+/** Opaque handle to a flag that cancels background segmentation tasks */
+export interface CancelFlagHandle {
+    readonly __brand: 'CancelFlagHandle';
+}
+
+/** Throws an Error if dict_name does not exist */
 export function segment(text: string, dict_name: string, safe: boolean, parallel: boolean): string[];
-/** file_path is an absolute path */
-export function loadDict(file_path: string, dict_name: string): string;
+/** file_path is an absolute path; returns false if dict_name already exists; throws an Error if the file cannot be read */
+export function loadDict(file_path: string, dict_name: string): boolean;
+
+/** Throws an Error if the file cannot be read or its content is invalid */
+export function tokenizerNew(file_path: string): TokenizerHandle;
+/** Throws a TypeError if an element of words is not a string */
+export function tokenizerFromWordList(words: string[]): TokenizerHandle;
+/** data can be compressed with gzip or zstd; throws an Error if the content is invalid */
+export function tokenizerFromBytes(data: Uint8Array): TokenizerHandle;
+export function tokenizerSegment(tokenizer: TokenizerHandle, text: string, safe: boolean, parallel: boolean): string[];
+/** Runs on the libuv thread pool; rejects with an AbortError if cancelled */
+export function tokenizerSegmentAsync(tokenizer: TokenizerHandle, text: string, safe: boolean, cancelFlag: CancelFlagHandle): Promise<string[]>;
+export function tokenizerAddWord(tokenizer: TokenizerHandle, words: string[]): void;
+export function tokenizerRemoveWord(tokenizer: TokenizerHandle, words: string[]): void;
+export function tokenizerContainsWord(tokenizer: TokenizerHandle, word: string): boolean;
+/** limit is undefined or null for no limit */
+export function tokenizerWordsWithPrefix(tokenizer: TokenizerHandle, prefix: string, limit: number | null | undefined, byFrequency: boolean): string[];
+export function tokenizerWords(tokenizer: TokenizerHandle): string[];
+
+export function cancelFlagNew(): CancelFlagHandle;
+export function cancelFlagCancel(cancelFlag: CancelFlagHandle): void;
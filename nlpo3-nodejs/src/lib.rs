@@ -1,69 +1,294 @@
 // SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Mutex;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use ahash::AHashMap as HashMap;
 use lazy_static::lazy_static;
 use neon::prelude::*;
-use nlpo3::tokenizer::{newmm::NewmmTokenizer, tokenizer_trait::Tokenizer};
+use neon::types::buffer::TypedArray;
+use nlpo3::tokenizer::newmm::{NewmmTokenizer, SegmentCancelled};
+use nlpo3::tokenizer::{
+    shared::SharedNewmmTokenizer, tokenizer_trait::Tokenizer, DictSource, WordOrder,
+};
 
 lazy_static! {
     static ref TOKENIZER_COLLECTION: Mutex<HashMap<String, Box<NewmmTokenizer>>> =
         Mutex::new(HashMap::new());
 }
 
+// A tokenizer owned by a JS object, freed when the object is garbage collected
+// and no background task is using it.
+// Background tasks segment with a snapshot, so adding or removing words
+// on the main thread never waits for them.
+struct BoxedTokenizer {
+    tokenizer: SharedNewmmTokenizer,
+}
+
+impl Finalize for BoxedTokenizer {}
+
+// A flag to cancel background segmentation tasks.
+struct BoxedCancelFlag {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Finalize for BoxedCancelFlag {}
+
+// Create a tokenizer using a dictionary file, one word per line,
+// possibly compressed with gzip or zstd.
+// Throws if the file cannot be read or its content is invalid.
+fn create_tokenizer<'a, C: Context<'a>>(cx: &mut C, file_path: &str) -> NeonResult<NewmmTokenizer> {
+    let error = match NewmmTokenizer::from_dict_source(DictSource::FilePath(file_path.into())) {
+        Ok(tokenizer) => return Ok(tokenizer),
+        Err(error) => error,
+    };
+    match error.downcast_ref::<io::Error>() {
+        // invalid UTF-8, or invalid compressed data
+        Some(io_error) if io_error.kind() != io::ErrorKind::InvalidData => cx.throw_error(format!(
+            "Cannot read dictionary file {}: {}",
+            file_path, io_error
+        )),
+        _ => cx.throw_error(format!(
+            "Invalid dictionary file {}: {:#}",
+            file_path, error
+        )),
+    }
+}
+
+fn to_js_array<'a, C: Context<'a>>(cx: &mut C, words: &[String]) -> JsResult<'a, JsArray> {
+    let js_array = JsArray::new(cx, words.len());
+    for (i, word) in words.iter().enumerate() {
+        let js_string = cx.string(word);
+        js_array.set(cx, i as u32, js_string)?;
+    }
+    Ok(js_array)
+}
+
+// Read an array of strings argument.
+// Throws a TypeError if an element is not a string.
+fn words_argument(cx: &mut FunctionContext, i: usize) -> NeonResult<Vec<String>> {
+    let js_words = cx.argument::<JsArray>(i)?.to_vec(cx)?;
+    js_words
+        .into_iter()
+        .map(|word| Ok(word.downcast_or_throw::<JsString, _>(cx)?.value(cx)))
+        .collect()
+}
+
+fn segment_with<'a, C: Context<'a>>(
+    cx: &mut C,
+    tokenizer: &NewmmTokenizer,
+    text: &str,
+    safe: bool,
+    parallel: bool,
+) -> JsResult<'a, JsArray> {
+    match tokenizer.segment(text, safe, parallel) {
+        Ok(result) => to_js_array(cx, &result),
+        Err(error) => cx.throw_error(error.to_string()),
+    }
+}
+
 // Load a dictionary file to a tokenizer,
 // and add that tokenizer to the tokenizer collection.
 //
 // Dictionary file must one word per line.
 // If successful, will insert a NewmmTokenizer to TOKENIZER_COLLECTION.
-// returns a tuple of string of loading result and a boolean
-fn load_dict(mut cx: FunctionContext) -> JsResult<JsString> {
-    let mut tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
+// returns true if loaded, false if the dictionary name already exists;
+// throws if the file cannot be read
+fn load_dict(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let file_path = cx.argument::<JsString>(0)?.value(&mut cx);
     let dict_name = cx.argument::<JsString>(1)?.value(&mut cx);
-    if let Some(_) = tokenizer_col_lock.get(&dict_name) {
-        Ok(cx.string(format!(
-            "Failed: dictionary {} exists, please use another name.",
-            dict_name
-        )))
-    } else {
-        let tokenizer = NewmmTokenizer::new(&file_path);
-        tokenizer_col_lock.insert(dict_name.to_owned(), Box::new(tokenizer));
-
-        Ok(cx.string(format!(
-            "Successful: dictionary name {} from file {} has been successfully loaded",
-            dict_name, file_path
-        )))
+    let mut tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
+    if tokenizer_col_lock.contains_key(&dict_name) {
+        return Ok(cx.boolean(false));
     }
+    let tokenizer = create_tokenizer(&mut cx, &file_path)?;
+    tokenizer_col_lock.insert(dict_name, Box::new(tokenizer));
+    Ok(cx.boolean(true))
 }
 
 // Break text into tokens.
 // Use newmm algorithm.
 /// Can use multithreading, but takes a lot of memory.
-/// returns an array of string
+/// returns an array of string;
+/// throws if the dictionary name does not exist
 fn segment(mut cx: FunctionContext) -> JsResult<JsArray> {
     let text = cx.argument::<JsString>(0)?.value(&mut cx);
     let dict_name = cx.argument::<JsString>(1)?.value(&mut cx);
     let safe = cx.argument::<JsBoolean>(2)?.value(&mut cx);
     let parallel = cx.argument::<JsBoolean>(3)?.value(&mut cx);
-    if let Some(loaded_tokenizer) = TOKENIZER_COLLECTION.lock().unwrap().get(&dict_name) {
-        let result = loaded_tokenizer.segment_to_string(&text, safe, parallel);
-        let js_result_array = JsArray::new(&mut cx, result.len() as u32);
-        for (i, obj) in result.iter().enumerate() {
-            let js_string = cx.string(obj);
-            js_result_array.set(&mut cx, i as u32, js_string).unwrap();
-        }
-        Ok(js_result_array)
-    } else {
-        panic!("Dictionary {} does not exist.", dict_name)
+    let tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
+    match tokenizer_col_lock.get(&dict_name) {
+        Some(loaded_tokenizer) => segment_with(&mut cx, loaded_tokenizer, &text, safe, parallel),
+        None => cx.throw_error(format!("Dictionary {} does not exist.", dict_name)),
+    }
+}
+
+// Create a boxed tokenizer using a dictionary file.
+fn tokenizer_new(mut cx: FunctionContext) -> JsResult<JsBox<BoxedTokenizer>> {
+    let file_path = cx.argument::<JsString>(0)?.value(&mut cx);
+    let tokenizer = create_tokenizer(&mut cx, &file_path)?;
+    Ok(cx.boxed(BoxedTokenizer {
+        tokenizer: SharedNewmmTokenizer::new(tokenizer),
+    }))
+}
+
+// Create a boxed tokenizer using an array of words.
+fn tokenizer_from_word_list(mut cx: FunctionContext) -> JsResult<JsBox<BoxedTokenizer>> {
+    let words = words_argument(&mut cx, 0)?;
+    Ok(cx.boxed(BoxedTokenizer {
+        tokenizer: SharedNewmmTokenizer::new(NewmmTokenizer::from_word_list(words)),
+    }))
+}
+
+// Create a boxed tokenizer using the content of a dictionary file,
+// from a Buffer or a Uint8Array, possibly compressed with gzip or zstd.
+// Throws if the content is invalid.
+fn tokenizer_from_bytes(mut cx: FunctionContext) -> JsResult<JsBox<BoxedTokenizer>> {
+    let data = cx.argument::<JsTypedArray<u8>>(0)?;
+    let result = NewmmTokenizer::from_bytes(data.as_slice(&cx));
+    match result {
+        Ok(tokenizer) => Ok(cx.boxed(BoxedTokenizer {
+            tokenizer: SharedNewmmTokenizer::new(tokenizer),
+        })),
+        Err(error) => cx.throw_error(format!("Invalid dictionary: {:#}", error)),
+    }
+}
+
+fn tokenizer_segment(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let text = cx.argument::<JsString>(1)?.value(&mut cx);
+    let safe = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let parallel = cx.argument::<JsBoolean>(3)?.value(&mut cx);
+    let tokenizer = boxed.tokenizer.snapshot();
+    segment_with(&mut cx, &tokenizer, &text, safe, parallel)
+}
+
+// Segment text like tokenizer_segment, checking the cancel flag while segmenting.
+// returns None if cancelled
+fn segment_cancellable(
+    tokenizer: &NewmmTokenizer,
+    text: &str,
+    safe: bool,
+    cancelled: &AtomicBool,
+) -> Result<Option<Vec<String>>, String> {
+    match tokenizer.segment_to_tokens_cancellable(text, safe, false, cancelled) {
+        Ok(tokens) => Ok(Some(tokens.into_iter().map(|token| token.text).collect())),
+        Err(error) if error.is::<SegmentCancelled>() => Ok(None),
+        Err(error) => Err(error.to_string()),
     }
 }
 
+// Segment text on the libuv thread pool.
+// returns a Promise of an array of string,
+// rejected with an AbortError if the cancel flag is set before it is done
+fn tokenizer_segment_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let tokenizer = cx
+        .argument::<JsBox<BoxedTokenizer>>(0)?
+        .tokenizer
+        .snapshot();
+    let text = cx.argument::<JsString>(1)?.value(&mut cx);
+    let safe = cx.argument::<JsBoolean>(2)?.value(&mut cx);
+    let cancelled = Arc::clone(&cx.argument::<JsBox<BoxedCancelFlag>>(3)?.cancelled);
+    let promise = cx
+        .task(move || segment_cancellable(&tokenizer, &text, safe, &cancelled))
+        .promise(|mut cx, result| match result {
+            Ok(Some(tokens)) => to_js_array(&mut cx, &tokens),
+            Ok(None) => {
+                let error = cx.error("Segmentation was cancelled.")?;
+                let name = cx.string("AbortError");
+                error.set(&mut cx, "name", name)?;
+                cx.throw(error)
+            }
+            Err(message) => cx.throw_error(message),
+        });
+    Ok(promise)
+}
+
+fn cancel_flag_new(mut cx: FunctionContext) -> JsResult<JsBox<BoxedCancelFlag>> {
+    Ok(cx.boxed(BoxedCancelFlag {
+        cancelled: Arc::new(AtomicBool::new(false)),
+    }))
+}
+
+fn cancel_flag_cancel(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let boxed = cx.argument::<JsBox<BoxedCancelFlag>>(0)?;
+    boxed.cancelled.store(true, Ordering::Relaxed);
+    Ok(cx.undefined())
+}
+
+fn tokenizer_add_word(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let words = words_argument(&mut cx, 1)?;
+    let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+    boxed.tokenizer.add_word(&words);
+    Ok(cx.undefined())
+}
+
+fn tokenizer_remove_word(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let words = words_argument(&mut cx, 1)?;
+    let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+    boxed.tokenizer.remove_word(&words);
+    Ok(cx.undefined())
+}
+
+fn tokenizer_contains_word(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let word = cx.argument::<JsString>(1)?.value(&mut cx);
+    let contains = boxed.tokenizer.snapshot().contains_word(&word);
+    Ok(cx.boolean(contains))
+}
+
+// Find dictionary words starting with a prefix.
+// limit is a number, or undefined or null for no limit.
+fn tokenizer_words_with_prefix(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let prefix = cx.argument::<JsString>(1)?.value(&mut cx);
+    let limit = cx.argument::<JsValue>(2)?;
+    let limit = if limit.is_a::<JsUndefined, _>(&mut cx) || limit.is_a::<JsNull, _>(&mut cx) {
+        None
+    } else {
+        Some(
+            limit
+                .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                .value(&mut cx) as usize,
+        )
+    };
+    let order = if cx.argument::<JsBoolean>(3)?.value(&mut cx) {
+        WordOrder::Frequency
+    } else {
+        WordOrder::Alphabetical
+    };
+    let words = boxed
+        .tokenizer
+        .snapshot()
+        .words_with_prefix(&prefix, limit, order);
+    to_js_array(&mut cx, &words)
+}
+
+fn tokenizer_words(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let boxed = cx.argument::<JsBox<BoxedTokenizer>>(0)?;
+    let words: Vec<String> = boxed.tokenizer.snapshot().words().collect();
+    to_js_array(&mut cx, &words)
+}
+
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("loadDict", load_dict)?;
     cx.export_function("segment", segment)?;
+    cx.export_function("tokenizerNew", tokenizer_new)?;
+    cx.export_function("tokenizerFromWordList", tokenizer_from_word_list)?;
+    cx.export_function("tokenizerFromBytes", tokenizer_from_bytes)?;
+    cx.export_function("tokenizerSegment", tokenizer_segment)?;
+    cx.export_function("tokenizerSegmentAsync", tokenizer_segment_async)?;
+    cx.export_function("tokenizerAddWord", tokenizer_add_word)?;
+    cx.export_function("tokenizerRemoveWord", tokenizer_remove_word)?;
+    cx.export_function("tokenizerContainsWord", tokenizer_contains_word)?;
+    cx.export_function("tokenizerWordsWithPrefix", tokenizer_words_with_prefix)?;
+    cx.export_function("tokenizerWords", tokenizer_words)?;
+    cx.export_function("cancelFlagNew", cancel_flag_new)?;
+    cx.export_function("cancelFlagCancel", cancel_flag_cancel)?;
     Ok(())
 }
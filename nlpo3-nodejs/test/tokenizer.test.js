@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+// Run with "npm test", which builds the native module and compiles index.ts first.
+
+const assert = require('node:assert')
+const path = require('node:path')
+const {test} = require('node:test')
+
+const nlpO3 = require('../nlpo3')
+
+const DICT_PATH = path.join(__dirname, '..', '..', 'words_th.txt')
+
+const isAbortError = (error) => error.name === 'AbortError'
+
+test('throws errors instead of crashing', () => {
+    assert.throws(() => new nlpO3.NewmmTokenizer('no_such_dict.txt'), /Cannot read dictionary file/)
+    assert.throws(() => new nlpO3.NewmmTokenizer(__dirname), /Cannot read dictionary file/)
+    assert.throws(() => nlpO3.NewmmTokenizer.fromBytes(Buffer.from([0xff, 0x0a])), /Invalid dictionary/)
+    assert.throws(() => nlpO3.NewmmTokenizer.fromWordList(['กิน', 42]), TypeError)
+    assert.throws(() => nlpO3.segment('กินข้าว', 'no_such_dict'), /does not exist/)
+})
+
+test('segments with a tokenizer object', async () => {
+    const tokenizer = new nlpO3.NewmmTokenizer(DICT_PATH)
+    assert.deepStrictEqual(tokenizer.segment('ไข่คน2021'), ['ไข่', 'คน', '2021'])
+    assert.deepStrictEqual(await tokenizer.segmentAsync('ไข่คน2021\nไข่คน'),
+        ['ไข่', 'คน', '2021', '\n', 'ไข่', 'คน'])
+
+    const other = nlpO3.NewmmTokenizer.fromWordList(['ไข่คน'])
+    other.addWord(['2021'])
+    assert.deepStrictEqual(other.segment('ไข่คน2021'), ['ไข่คน', '2021'])
+    other.removeWord(['ไข่คน'])
+    assert.strictEqual(other.containsWord('ไข่คน'), false)
+})
+
+test('segments asynchronously like synchronously', async () => {
+    const tokenizer = new nlpO3.NewmmTokenizer(DICT_PATH)
+    // in safe mode, this text is cut into parts that do not end at a newline,
+    // and segmenting it line by line gives other tokens
+    const text = [
+        'ารบ้านคนไป',
+        'มนกที่',
+        'ไข่ไข่',
+        'ที่กรไข่ตาาตากลมมกลมกลมไข่าไข่นกะกลมมมาเที่ยวกรกรไปกินรไข่ไข่',
+        'ะ',
+        'ะข้าวที่นนการคลมร',
+        'านตากิน',
+        'ข้าที่ยวที่กรที่คนกกลมไปะ',
+    ].join('\n')
+    for (const safe of [false, true]) {
+        assert.deepStrictEqual(await tokenizer.segmentAsync(text, {safe}), tokenizer.segment(text, safe))
+    }
+})
+
+test('segments a batch with at most "concurrency" texts at the same time', async () => {
+    const tokenizer = nlpO3.NewmmTokenizer.fromWordList(['กิน', 'ข้าว', 'น้ำ'])
+    const segmentAsync = tokenizer.segmentAsync.bind(tokenizer)
+    let running = 0
+    let maxRunning = 0
+    tokenizer.segmentAsync = async (text, options) => {
+        running++
+        maxRunning = Math.max(maxRunning, running)
+        try {
+            return await segmentAsync(text, options)
+        } finally {
+            running--
+        }
+    }
+    const texts = Array.from({length: 10}, (_, i) => (i % 2 ? 'กินข้าว' : 'กินน้ำ'))
+    const results = await tokenizer.segmentBatchAsync(texts, {concurrency: 3})
+    assert.strictEqual(maxRunning, 3)
+    assert.deepStrictEqual(results, texts.map((text) => (text === 'กินข้าว' ? ['กิน', 'ข้าว'] : ['กิน', 'น้ำ'])))
+
+    await assert.rejects(tokenizer.segmentBatchAsync(texts, {concurrency: 0}), RangeError)
+})
+
+test('rejects with an AbortError when cancelled', async () => {
+    const tokenizer = nlpO3.NewmmTokenizer.fromWordList(['กิน', 'ข้าว'])
+    const aborted = new AbortController()
+    aborted.abort()
+    await assert.rejects(tokenizer.segmentAsync('กินข้าว', {signal: aborted.signal}), isAbortError)
+
+    // a large text without newlines is cancelled between parts in safe mode
+    const text = 'กินข้าว'.repeat(20000)
+    const controller = new AbortController()
+    const segmented = tokenizer.segmentAsync(text, {safe: true, signal: controller.signal})
+    controller.abort()
+    await assert.rejects(segmented, isAbortError)
+
+    const batchController = new AbortController()
+    const batch = tokenizer.segmentBatchAsync([text, text, text], {safe: true, signal: batchController.signal})
+    batchController.abort()
+    await assert.rejects(batch, isAbortError)
+})
+
+test('changes the dictionary while a background task is running', async () => {
+    const tokenizer = nlpO3.NewmmTokenizer.fromWordList(['กิน', 'ข้าว'])
+    const text = 'กินข้าว'.repeat(2000)
+    const segmented = tokenizer.segmentAsync(text, {safe: true})
+    // does not wait for the running task, which keeps the dictionary it started with
+    tokenizer.addWord(['กินข้าว'])
+    assert.deepStrictEqual(tokenizer.segment('กินข้าว'), ['กินข้าว'])
+    const tokens = await segmented
+    assert.strictEqual(tokens.length, 4000)
+})
@@ -10,13 +10,23 @@
 # Arthit Suriyawongkul
 
 from pathlib import Path
-from typing import List, Tuple
+from typing import Dict, Iterable, List, Tuple
 
 # import from .so (Rust)
-from ._nlpo3_python_backend import load_dict as rust_load_dict
-from ._nlpo3_python_backend import segment as rust_segment
+from ._nlpo3_python_backend import NewmmTokenizer
 
-# TODO: load_dict from in-memory list of words
+__all__ = [
+    "NewmmTokenizer",
+    "add_word",
+    "load_dict",
+    "load_dict_from_words",
+    "remove_word",
+    "segment",
+    "segment_batch",
+]
+
+# tokenizers loaded by load_dict(), by dictionary name
+_TOKENIZERS: Dict[str, NewmmTokenizer] = {}
 
 
 def load_dict(file_path: str, dict_name: str) -> Tuple[str, bool]:
@@ -26,15 +36,104 @@ def load_dict(file_path: str, dict_name: str) -> Tuple[str, bool]:
     and assigned dict_name to it.
     *** This function does not override an existing dict name. ***
 
+    For a tokenizer with its own lifetime, use NewmmTokenizer instead.
+
     :param file_path: Path to a dictionary file
     :type file_path: str
     :param dict_name: A unique dictionary name, use for reference.
     :type dict_name: str
     :return tuple[human_readable_result_str, bool]
     """
+    if dict_name in _TOKENIZERS:
+        return (
+            f"Failed: dictionary name {dict_name} already exists, "
+            "please use another name.",
+            False,
+        )
+
     path = Path(file_path).resolve()
+    _TOKENIZERS[dict_name] = NewmmTokenizer(str(path))
+
+    return (
+        f"Successful: file {path} has been successfully loaded "
+        f"to dictionary name {dict_name}.",
+        True,
+    )
+
+
+def load_dict_from_words(
+    words: Iterable[str], dict_name: str
+) -> Tuple[str, bool]:
+    """Load dictionary from an iterable of words.
+
+    Load words into an in-memory dictionary collection,
+    and assigned dict_name to it.
+    *** This function does not override an existing dict name. ***
+
+    :param words: Dictionary words
+    :type words: Iterable[str]
+    :param dict_name: A unique dictionary name, use for reference.
+    :type dict_name: str
+    :return tuple[human_readable_result_str, bool]
+    """
+    if dict_name in _TOKENIZERS:
+        return (
+            f"Failed: dictionary name {dict_name} already exists, "
+            "please use another name.",
+            False,
+        )
+
+    _TOKENIZERS[dict_name] = NewmmTokenizer.from_word_list(words)
+
+    return (
+        f"Successful: words have been successfully loaded "
+        f"to dictionary name {dict_name}.",
+        True,
+    )
+
+
+def add_word(words: Iterable[str], dict_name: str) -> Tuple[str, bool]:
+    """Add words to an existing dictionary.
+
+    :param words: Words to add
+    :type words: Iterable[str]
+    :param dict_name: Dictionary name, as assigned in load_dict()
+    :type dict_name: str
+    :return tuple[human_readable_result_str, bool]
+    """
+    tokenizer = _TOKENIZERS.get(dict_name)
+    if tokenizer is None:
+        return (
+            "Cannot add new word(s) - "
+            f"dictionary name {dict_name} does not exist.",
+            False,
+        )
+
+    tokenizer.add_word(words)
 
-    return rust_load_dict(str(path), dict_name)
+    return ("Add new word(s) successfully.", True)
+
+
+def remove_word(words: Iterable[str], dict_name: str) -> Tuple[str, bool]:
+    """Remove words from an existing dictionary.
+
+    :param words: Words to remove
+    :type words: Iterable[str]
+    :param dict_name: Dictionary name, as assigned in load_dict()
+    :type dict_name: str
+    :return tuple[human_readable_result_str, bool]
+    """
+    tokenizer = _TOKENIZERS.get(dict_name)
+    if tokenizer is None:
+        return (
+            "Cannot remove word(s) - "
+            f"dictionary name {dict_name} does not exist.",
+            False,
+        )
+
+    tokenizer.remove_word(words)
+
+    return ("Remove word(s) successfully.", True)
 
 
 def segment(
@@ -60,10 +159,42 @@ def segment(
     :type parallel: bool, optional
     :return: List of tokens
     :rtype: List[str]
+    :raises RuntimeError: If dictionary name does not exist
     """
     if not text or not isinstance(text, str):
         return []
 
-    result = rust_segment(text, dict_name, safe, parallel)
+    tokenizer = _TOKENIZERS.get(dict_name)
+    if tokenizer is None:
+        raise RuntimeError(f"Dictionary name {dict_name} does not exist.")
+
+    return tokenizer.segment(text, safe, parallel)
+
+
+def segment_batch(
+    texts: List[str],
+    dict_name: str,
+    safe: bool = False,
+) -> List[List[str]]:
+    """Break each text into tokens.
+
+    Texts are segmented in parallel on Rust threads,
+    without holding the Python global interpreter lock (GIL).
+
+    :param texts: Input texts
+    :type texts: List[str]
+    :param dict_name: Dictionary name, as assigned in load_dict()
+    :type dict_name: str
+    :param safe: Use safe mode to avoid long waiting time in
+        a text with lots of ambiguous word boundaries,
+        defaults to False
+    :type safe: bool, optional
+    :return: List of tokens for each text
+    :rtype: List[List[str]]
+    :raises RuntimeError: If dictionary name does not exist
+    """
+    tokenizer = _TOKENIZERS.get(dict_name)
+    if tokenizer is None:
+        raise RuntimeError(f"Dictionary name {dict_name} does not exist.")
 
-    return result
+    return tokenizer.segment_batch(texts, safe)
@@ -3,43 +3,258 @@
 
 """Type stubs for _nlpo3_python_backend Rust extension module."""
 
-from typing import List, Tuple
+import os
+from typing import Dict, Iterable, List, Literal, Optional, Tuple, Union
 
-def load_dict(file_path: str, dict_name: str) -> Tuple[str, bool]:
-    """Load a dictionary file to a tokenizer.
+class NewmmTokenizer:
+    """Dictionary-based maximal matching word tokenizer.
 
-    Load a dictionary file into an in-memory dictionary collection,
-    and assign dict_name to it. This function does not override an
-    existing dict name.
+    Each object owns its dictionary, which is freed when the object
+    is garbage collected.
 
-    Args:
-        file_path: Path to a dictionary file (one word per line)
-        dict_name: A unique dictionary name, used for reference
-
-    Returns:
-        A tuple of (human_readable_result_str, success_bool)
-    """
-    ...
-
-def segment(
-    text: str,
-    dict_name: str,
-    safe: bool = False,
-    parallel: bool = False,
-) -> List[str]:
-    """Break text into tokens using newmm algorithm.
-
-    Args:
-        text: Input text to segment
-        dict_name: Dictionary name, as assigned in load_dict()
-        safe: Use safe mode to avoid long waiting time in a text with
-              lots of ambiguous word boundaries (default: False)
-        parallel: Use multithread mode (default: False)
-
-    Returns:
-        List of tokens
-
-    Raises:
-        RuntimeError: If dictionary name does not exist
+    The GIL is released while segmenting, so an object can be shared
+    by Python threads.
     """
-    ...
+
+    def __init__(self, dict_path: Union[str, os.PathLike]) -> None:
+        """Create a tokenizer using a dictionary file.
+
+        Args:
+            dict_path: Path to a dictionary file (one word per line),
+                       possibly compressed with gzip or zstd
+
+        Raises:
+            OSError: If the dictionary file cannot be read
+            ValueError: If the dictionary content is invalid
+        """
+        ...
+
+    @staticmethod
+    def from_bytes(data: bytes) -> "NewmmTokenizer":
+        """Create a tokenizer using the content of a dictionary file.
+
+        Args:
+            data: Dictionary file content, possibly compressed
+                  with gzip or zstd
+
+        Returns:
+            A new tokenizer
+
+        Raises:
+            ValueError: If the dictionary content is invalid
+        """
+        ...
+
+    @staticmethod
+    def from_word_list(words: Iterable[str]) -> "NewmmTokenizer":
+        """Create a tokenizer using an iterable of words.
+
+        Args:
+            words: Dictionary words
+
+        Returns:
+            A new tokenizer
+
+        Raises:
+            TypeError: If words is a str or contains a non-str item
+        """
+        ...
+
+    def segment(
+        self,
+        text: str,
+        safe: bool = False,
+        parallel: bool = False,
+    ) -> List[str]:
+        """Break text into tokens using newmm algorithm.
+
+        Args:
+            text: Input text to segment
+            safe: Use safe mode to avoid long waiting time in a text with
+                  lots of ambiguous word boundaries (default: False)
+            parallel: Use multithread mode (default: False)
+
+        Returns:
+            List of tokens
+        """
+        ...
+
+    def segment_batch(
+        self,
+        texts: List[str],
+        safe: bool = False,
+    ) -> List[List[str]]:
+        """Break each text into tokens, in parallel on Rust threads.
+
+        Args:
+            texts: Input texts to segment
+            safe: Use safe mode to avoid long waiting time in a text with
+                  lots of ambiguous word boundaries (default: False)
+
+        Returns:
+            List of tokens for each text
+        """
+        ...
+
+    def add_word(self, words: Iterable[str]) -> None:
+        """Add words to the dictionary.
+
+        Args:
+            words: Words to add
+
+        Raises:
+            TypeError: If words is a str or contains a non-str item
+        """
+        ...
+
+    def contains_word(self, word: str) -> bool:
+        """Check whether a word is in the dictionary.
+
+        Args:
+            word: Word to look up
+
+        Returns:
+            True if the word is in the dictionary
+        """
+        ...
+
+    def words_with_prefix(
+        self,
+        prefix: str,
+        limit: Optional[int] = None,
+        order: Literal["alphabetical", "frequency"] = "alphabetical",
+    ) -> List[str]:
+        """Find dictionary words starting with prefix, for autocomplete.
+
+        Args:
+            prefix: Beginning of the words, an empty prefix matches all
+            limit: Maximum number of words (default: no limit)
+            order: "alphabetical" for Unicode code point order, or
+                   "frequency" for highest frequency first, words
+                   without a frequency last (default: "alphabetical")
+
+        Returns:
+            List of words
+
+        Raises:
+            ValueError: If order is unknown
+        """
+        ...
+
+    def words(self) -> List[str]:
+        """List all dictionary words, in Unicode code point order.
+
+        Returns:
+            List of words
+        """
+        ...
+
+    def suggest_spelling(
+        self,
+        word: str,
+        max_distance: float = 1.0,
+        max_suggestions: int = 5,
+    ) -> List[str]:
+        """Suggest corrections of a word, best first.
+
+        Corrections are the closest dictionary words, then the most
+        frequent ones. A wrong tone mark or a consonant with the same
+        sound costs 0.5, other edits cost 1.
+
+        Args:
+            word: Word to correct
+            max_distance: Maximum edit distance (default: 1.0)
+            max_suggestions: Maximum number of suggestions (default: 5)
+
+        Returns:
+            List of suggested words
+        """
+        ...
+
+    def check_spelling(
+        self,
+        text: str,
+        max_distance: float = 1.0,
+        max_suggestions: int = 5,
+    ) -> List[Tuple[int, int, str, List[str]]]:
+        """Find misspelled words: Thai text not in the dictionary.
+
+        Args:
+            text: Text to check
+            max_distance: Maximum edit distance of a suggestion
+                          (default: 1.0)
+            max_suggestions: Maximum number of suggestions per word
+                             (default: 5)
+
+        Returns:
+            List of (start, end, word, suggestions), where start and end
+            are character indices in text
+        """
+        ...
+
+    def correct_spelling(self, text: str, max_distance: float = 1.0) -> str:
+        """Replace each misspelled word with its best correction.
+
+        Args:
+            text: Text to correct
+            max_distance: Maximum edit distance of a correction
+                          (default: 1.0)
+
+        Returns:
+            Corrected text
+        """
+        ...
+
+    def romanize(
+        self,
+        text: str,
+        engine: Literal["rtgs", "ipa"] = "rtgs",
+        exceptions: Optional[Dict[str, str]] = None,
+    ) -> str:
+        """Romanize a text, with Thai words separated by a space.
+
+        Words are cut into syllables with spelling rules, so vowels
+        that are not written, as in มหาวิทยาลัย, are missed:
+        give such words in exceptions.
+
+        Args:
+            text: Text to romanize
+            engine: "rtgs" for the Royal Thai General System,
+                    "ipa" for the International Phonetic Alphabet,
+                    with tones (default: "rtgs")
+            exceptions: Romanization of words, used instead of the rules
+
+        Returns:
+            Romanized text; other text is kept,
+            with Thai digits as ASCII digits
+
+        Raises:
+            ValueError: If engine is not "rtgs" or "ipa"
+        """
+        ...
+
+    def slug(
+        self, text: str, exceptions: Optional[Dict[str, str]] = None
+    ) -> str:
+        """Make a URL slug of a text, from its RTGS romanization.
+
+        Args:
+            text: Text to make a slug of
+            exceptions: RTGS romanization of words,
+                        used instead of the rules
+
+        Returns:
+            Lowercase ASCII letters and digits, words separated by "-"
+        """
+        ...
+
+    def remove_word(self, words: Iterable[str]) -> None:
+        """Remove words from the dictionary.
+
+        Args:
+            words: Words to remove
+
+        Raises:
+            TypeError: If words is a str or contains a non-str item
+        """
+        ...
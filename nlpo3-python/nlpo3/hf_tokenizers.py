@@ -0,0 +1,74 @@
+# SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+# SPDX-License-Identifier: Apache-2.0
+
+# Pre-tokenizer for the Hugging Face tokenizers library.
+#
+# Splits text into Thai words before a subword model (BPE, WordPiece, Unigram).
+# Requires the tokenizers package, which is not a dependency of nlpo3.
+
+from typing import Any, List
+
+from ._nlpo3_python_backend import NewmmTokenizer
+
+__all__ = ["NewmmPreTokenizer"]
+
+
+class NewmmPreTokenizer:
+    """Pre-tokenizer backed by a NewmmTokenizer.
+
+    Attach it to a tokenizers.Tokenizer with PreTokenizer.custom():
+
+        from tokenizers.pre_tokenizers import PreTokenizer
+
+        tokenizer.pre_tokenizer = PreTokenizer.custom(
+            NewmmPreTokenizer(NewmmTokenizer("path/to/dict.file"))
+        )
+
+    Each split is a slice of the normalized string, so the offsets of
+    the subword tokens map back to the original text.
+
+    A tokenizer with a custom pre-tokenizer cannot be saved to JSON;
+    set another pre-tokenizer before saving.
+
+    :param tokenizer: Tokenizer used to split text into words
+    :type tokenizer: NewmmTokenizer
+    :param safe: Use safe mode to avoid long waiting time in
+        a text with lots of ambiguous word boundaries,
+        defaults to True
+    :type safe: bool, optional
+    :param keep_whitespace: Keep whitespace and line break splits,
+        instead of dropping them, defaults to False
+    :type keep_whitespace: bool, optional
+    """
+
+    def __init__(
+        self,
+        tokenizer: NewmmTokenizer,
+        safe: bool = True,
+        keep_whitespace: bool = False,
+    ) -> None:
+        self.tokenizer = tokenizer
+        self.safe = safe
+        self.keep_whitespace = keep_whitespace
+
+    def split(self, i: int, normalized_string: Any) -> List[Any]:
+        """Split a tokenizers.NormalizedString into words.
+
+        Tokens of the newmm tokenizer join back to the input text,
+        so their lengths give the character offsets of the slices.
+        """
+        tokens = self.tokenizer.segment(
+            normalized_string.normalized, self.safe
+        )
+        splits = []
+        start = 0
+        for token in tokens:
+            end = start + len(token)
+            if self.keep_whitespace or token.strip():
+                splits.append(normalized_string[start:end])
+            start = end
+        return splits
+
+    def pre_tokenize(self, pretok: Any) -> None:
+        """Split a tokenizers.PreTokenizedString in place."""
+        pretok.split(self.split)
@@ -10,122 +10,356 @@
  * Thanathip Suntorntip
  * Arthit Suriyawongkul
  */
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
 
-use ahash::AHashMap as HashMap;
-use lazy_static::lazy_static;
+use nlpo3::romanize::{Engine, Romanizer};
+use nlpo3::spell::SpellChecker;
 use nlpo3::tokenizer::newmm::NewmmTokenizer;
+use nlpo3::tokenizer::shared::SharedNewmmTokenizer;
 use nlpo3::tokenizer::tokenizer_trait::Tokenizer;
+use nlpo3::tokenizer::{DictSource, WordOrder};
+use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::PyString;
-use pyo3::{exceptions, wrap_pyfunction};
+use rayon::prelude::*;
 
-lazy_static! {
-    static ref TOKENIZER_COLLECTION: Mutex<HashMap<String, Box<NewmmTokenizer>>> =
-        Mutex::new(HashMap::new());
-}
-
-/// Load a dictionary file to a tokenizer,
-/// and add that tokenizer to the tokenizer collection.
+/// Dictionary-based maximal matching word tokenizer.
+///
+/// Each object owns its dictionary,
+/// which is freed when the object is garbage collected.
 ///
-/// Dictionary file must one word per line.
-/// If successful, will insert a NewmmTokenizer to TOKENIZER_COLLECTION.
-/// returns a tuple of string of loading result and a boolean
+/// The GIL is released while segmenting, so an object can be shared
+/// by Python threads. Dictionary changes (add_word, remove_word)
+/// do not wait for running segmentations, which keep using
+/// the dictionary they started with.
 ///
-/// signature: (file_path: str, dict_name: str) -> (str, boolean)
-#[pyfunction]
-#[pyo3(signature = (file_path, dict_name))]
-fn load_dict(file_path: &str, dict_name: &str) -> PyResult<(String, bool)> {
-    let mut tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
-    if tokenizer_col_lock.get(dict_name).is_some() {
-        Ok((
-            format!(
-                "Failed: dictionary name {} already exists, please use another name.",
-                dict_name
-            ),
-            false,
-        ))
-    } else {
-        let tokenizer = NewmmTokenizer::new(file_path);
-        tokenizer_col_lock.insert(dict_name.to_owned(), Box::new(tokenizer));
-
-        Ok((
-            format!(
-                "Successful: file {} has been successfully loaded to dictionary name {}.",
-                file_path, dict_name
-            ),
-            true,
-        ))
+/// signature: (dict_path: str | os.PathLike) -> NewmmTokenizer
+#[pyclass(name = "NewmmTokenizer", module = "nlpo3", frozen)]
+struct PyNewmmTokenizer {
+    tokenizer: SharedNewmmTokenizer,
+}
+
+/// Collect words from a Python iterable of str.
+/// A str is rejected, instead of being read as a sequence of characters.
+fn extract_words(words: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    if words.is_instance_of::<PyString>() {
+        return Err(exceptions::PyTypeError::new_err(
+            "Expected an iterable of words, got str.",
+        ));
     }
+    words
+        .try_iter()?
+        .map(|word| word?.extract::<String>())
+        .collect()
 }
 
-/// Break text into tokens.
-/// Use newmm algorithm.
-/// Can use multithreading, but takes a lot of memory.
-/// returns list of valid utf-8 bytes list
-///
-/// signature: (text: str, dict_name: str, safe: boolean = false, parallel: boolean = false) -> List[List[u8]]
-///
-#[pyfunction]
-#[pyo3(signature = (text, dict_name, safe=false, parallel=false))]
-fn segment(
-    text: &Bound<'_, PyString>,
-    dict_name: &str,
-    safe: bool,
-    parallel: bool,
-) -> PyResult<Vec<String>> {
-    if let Some(loaded_tokenizer) = TOKENIZER_COLLECTION.lock().unwrap().get(dict_name) {
-        let result = loaded_tokenizer.segment_to_string(text.to_str()?, safe, parallel);
-        Ok(result)
-    } else {
-        Err(exceptions::PyRuntimeError::new_err(format!(
-            "Dictionary name {} does not exist.",
-            dict_name
-        )))
+/// A misspelled word: (start, end, word, suggestions)
+type PyMisspelling = (usize, usize, String, Vec<String>);
+
+fn runtime_error(error: anyhow::Error) -> PyErr {
+    exceptions::PyRuntimeError::new_err(error.to_string())
+}
+
+fn value_error(error: anyhow::Error) -> PyErr {
+    // "{:#}" includes the causes, such as the invalid dictionary line
+    exceptions::PyValueError::new_err(format!("{:#}", error))
+}
+
+/// OSError if a dictionary file cannot be read, ValueError if its content is invalid.
+fn dict_error(error: anyhow::Error) -> PyErr {
+    match error.downcast::<io::Error>() {
+        // invalid UTF-8, or invalid compressed data
+        Ok(error) if error.kind() == io::ErrorKind::InvalidData => value_error(error.into()),
+        Ok(error) => error.into(),
+        Err(error) => value_error(error),
     }
 }
 
-/*
-/// Add words to existing dictionary
-#[pyfunction]
-fn add_word(dict_name: &str, words: Vec<&str>) -> PyResult<(String, bool)> {
-    let mut tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
-    if let Some(newmm_dict) = tokenizer_col_lock.get(dict_name) {
-        newmm_dict.add_word(&words);
-        Ok((format!("Add new word(s) successfully."), true))
-    } else {
-        Ok((
-            format!(
-                "Cannot add new word(s) - dictionary instance named '{}' does not exist.",
-                dict_name
-            ),
-            false,
-        ))
+#[pymethods]
+impl PyNewmmTokenizer {
+    /// Create a tokenizer using a dictionary file, one word per line.
+    /// The file can be compressed with gzip or zstd.
+    /// Raises OSError if the file cannot be read,
+    /// ValueError if its content is invalid.
+    #[new]
+    #[pyo3(signature = (dict_path))]
+    fn new(dict_path: PathBuf) -> PyResult<Self> {
+        let tokenizer = NewmmTokenizer::from_dict_source(DictSource::FilePath(dict_path))
+            .map_err(dict_error)?;
+        Ok(Self {
+            tokenizer: SharedNewmmTokenizer::new(tokenizer),
+        })
+    }
+
+    /// Create a tokenizer using the content of a dictionary file,
+    /// for example package data read with importlib.resources.
+    /// The content can be compressed with gzip or zstd.
+    /// Raises ValueError if the content is invalid.
+    ///
+    /// signature: (data: bytes) -> NewmmTokenizer
+    #[staticmethod]
+    #[pyo3(signature = (data))]
+    fn from_bytes(py: Python<'_>, data: &[u8]) -> PyResult<Self> {
+        let tokenizer = py
+            .detach(|| NewmmTokenizer::from_bytes(data))
+            .map_err(value_error)?;
+        Ok(Self {
+            tokenizer: SharedNewmmTokenizer::new(tokenizer),
+        })
+    }
+
+    /// Create a tokenizer using an iterable of words.
+    ///
+    /// signature: (words: Iterable[str]) -> NewmmTokenizer
+    #[staticmethod]
+    #[pyo3(signature = (words))]
+    fn from_word_list(words: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            tokenizer: SharedNewmmTokenizer::new(NewmmTokenizer::from_word_list(extract_words(
+                words,
+            )?)),
+        })
+    }
+
+    /// Break text into tokens.
+    /// Use newmm algorithm.
+    /// Can use multithreading, but takes a lot of memory.
+    ///
+    /// signature: (text: str, safe: boolean = false, parallel: boolean = false) -> List[str]
+    #[pyo3(signature = (text, safe=false, parallel=false))]
+    fn segment(
+        &self,
+        py: Python<'_>,
+        text: &Bound<'_, PyString>,
+        safe: bool,
+        parallel: bool,
+    ) -> PyResult<Vec<String>> {
+        let text = text.to_str()?;
+        py.detach(|| {
+            self.tokenizer
+                .snapshot()
+                .segment(text, safe, parallel)
+                .map_err(|error| exceptions::PyRuntimeError::new_err(error.to_string()))
+        })
+    }
+
+    /// Break each text into tokens.
+    /// Texts are segmented in parallel, on Rust threads.
+    ///
+    /// signature: (texts: List[str], safe: boolean = false) -> List[List[str]]
+    #[pyo3(signature = (texts, safe=false))]
+    fn segment_batch(
+        &self,
+        py: Python<'_>,
+        texts: Vec<String>,
+        safe: bool,
+    ) -> PyResult<Vec<Vec<String>>> {
+        py.detach(|| {
+            let tokenizer = self.tokenizer.snapshot();
+            texts
+                .par_iter()
+                .map(|text| tokenizer.segment(text, safe, false))
+                .collect::<Result<Vec<Vec<String>>, _>>()
+                .map_err(|error| exceptions::PyRuntimeError::new_err(error.to_string()))
+        })
+    }
+
+    /// Add words to the dictionary.
+    ///
+    /// signature: (words: Iterable[str]) -> None
+    #[pyo3(signature = (words))]
+    fn add_word(&self, py: Python<'_>, words: &Bound<'_, PyAny>) -> PyResult<()> {
+        let words = extract_words(words)?;
+        let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+        py.detach(|| self.tokenizer.add_word(&words));
+        Ok(())
+    }
+
+    /// Whether a word is in the dictionary.
+    ///
+    /// signature: (word: str) -> bool
+    #[pyo3(signature = (word))]
+    fn contains_word(&self, word: &str) -> bool {
+        self.tokenizer.snapshot().contains_word(word)
+    }
+
+    /// Dictionary words starting with prefix, at most limit of them.
+    /// order is "alphabetical" (Unicode code point order) or "frequency"
+    /// (highest first, words without a frequency last).
+    ///
+    /// signature: (prefix: str, limit: Optional[int] = None, order: str = "alphabetical") -> List[str]
+    #[pyo3(signature = (prefix, limit=None, order="alphabetical"))]
+    fn words_with_prefix(
+        &self,
+        prefix: &str,
+        limit: Option<usize>,
+        order: &str,
+    ) -> PyResult<Vec<String>> {
+        let order = match order {
+            "alphabetical" => WordOrder::Alphabetical,
+            "frequency" => WordOrder::Frequency,
+            _ => {
+                return Err(exceptions::PyValueError::new_err(format!(
+                    "Unknown order {:?}, expected \"alphabetical\" or \"frequency\".",
+                    order
+                )))
+            }
+        };
+        Ok(self
+            .tokenizer
+            .snapshot()
+            .words_with_prefix(prefix, limit, order))
+    }
+
+    /// All dictionary words, in Unicode code point order.
+    ///
+    /// signature: () -> List[str]
+    fn words(&self) -> Vec<String> {
+        self.tokenizer.snapshot().words().collect()
+    }
+
+    /// Corrections of a word, best first: the closest dictionary words,
+    /// then the most frequent ones.
+    ///
+    /// signature: (word: str, max_distance: float = 1.0, max_suggestions: int = 5) -> List[str]
+    #[pyo3(signature = (word, max_distance=1.0, max_suggestions=5))]
+    fn suggest_spelling(
+        &self,
+        py: Python<'_>,
+        word: &str,
+        max_distance: f32,
+        max_suggestions: usize,
+    ) -> PyResult<Vec<String>> {
+        let checker = self.spell_checker(max_distance, max_suggestions);
+        Ok(py.detach(|| {
+            checker
+                .suggest(word)
+                .into_iter()
+                .map(|suggestion| suggestion.word)
+                .collect()
+        }))
+    }
+
+    /// Find the misspelled words of a text: runs of Thai text
+    /// that are not dictionary words.
+    /// Returns (start, end, word, suggestions) tuples,
+    /// start and end are character indices.
+    ///
+    /// signature: (text: str, max_distance: float = 1.0, max_suggestions: int = 5) -> List[Tuple[int, int, str, List[str]]]
+    #[pyo3(signature = (text, max_distance=1.0, max_suggestions=5))]
+    fn check_spelling(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        max_distance: f32,
+        max_suggestions: usize,
+    ) -> PyResult<Vec<PyMisspelling>> {
+        let checker = self.spell_checker(max_distance, max_suggestions);
+        let misspellings = py.detach(|| checker.check(text)).map_err(runtime_error)?;
+        Ok(misspellings
+            .into_iter()
+            .map(|misspelling| {
+                let suggestions = misspelling
+                    .suggestions
+                    .into_iter()
+                    .map(|suggestion| suggestion.word)
+                    .collect();
+                (
+                    misspelling.start,
+                    misspelling.end,
+                    misspelling.text,
+                    suggestions,
+                )
+            })
+            .collect())
+    }
+
+    /// Replace each misspelled word of a text with its best correction.
+    ///
+    /// signature: (text: str, max_distance: float = 1.0) -> str
+    #[pyo3(signature = (text, max_distance=1.0))]
+    fn correct_spelling(&self, py: Python<'_>, text: &str, max_distance: f32) -> PyResult<String> {
+        let checker = self.spell_checker(max_distance, 1);
+        py.detach(|| checker.correct(text)).map_err(runtime_error)
+    }
+
+    /// Romanize a text, with Thai words separated by a space.
+    /// engine is "rtgs" (Royal Thai General System) or "ipa"
+    /// (International Phonetic Alphabet, with tones).
+    /// exceptions maps words to their romanization, used instead of the rules.
+    ///
+    /// signature: (text: str, engine: str = "rtgs", exceptions: Optional[Dict[str, str]] = None) -> str
+    #[pyo3(signature = (text, engine="rtgs", exceptions=None))]
+    fn romanize(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        engine: &str,
+        exceptions: Option<HashMap<String, String>>,
+    ) -> PyResult<String> {
+        let engine = match engine {
+            "rtgs" => Engine::Rtgs,
+            "ipa" => Engine::Ipa,
+            _ => {
+                return Err(exceptions::PyValueError::new_err(format!(
+                    "Unknown engine {:?}, expected \"rtgs\" or \"ipa\".",
+                    engine
+                )))
+            }
+        };
+        let romanizer = self.romanizer(engine, exceptions.unwrap_or_default());
+        py.detach(|| romanizer.romanize(text))
+            .map_err(runtime_error)
+    }
+
+    /// A URL slug of a text: its RTGS romanization in lowercase
+    /// ASCII letters and digits, with words separated by "-".
+    ///
+    /// signature: (text: str, exceptions: Optional[Dict[str, str]] = None) -> str
+    #[pyo3(signature = (text, exceptions=None))]
+    fn slug(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        exceptions: Option<HashMap<String, String>>,
+    ) -> PyResult<String> {
+        let romanizer = self.romanizer(Engine::Rtgs, exceptions.unwrap_or_default());
+        py.detach(|| romanizer.slug(text)).map_err(runtime_error)
+    }
+
+    /// Remove words from the dictionary.
+    ///
+    /// signature: (words: Iterable[str]) -> None
+    #[pyo3(signature = (words))]
+    fn remove_word(&self, py: Python<'_>, words: &Bound<'_, PyAny>) -> PyResult<()> {
+        let words = extract_words(words)?;
+        let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+        py.detach(|| self.tokenizer.remove_word(&words));
+        Ok(())
     }
 }
 
-/// Remove words from existing dictionary
-#[pyfunction]
-fn remove_word(dict_name: &str, words: Vec<&str>) -> PyResult<(String, bool)> {
-    let mut tokenizer_col_lock = TOKENIZER_COLLECTION.lock().unwrap();
-    if let Some(newmm_dict) = tokenizer_col_lock.get(dict_name) {
-        newmm_dict.remove_word(&words);
-        Ok((format!("Remove word(s) successfully."), true))
-    } else {
-        Ok((
-            format!(
-                "Cannot remove word(s) - dictionary instance named '{}' does not exist.",
-                dict_name
-            ),
-            false,
-        ))
+impl PyNewmmTokenizer {
+    /// A spelling checker with a snapshot of the dictionary.
+    fn spell_checker(&self, max_distance: f32, max_suggestions: usize) -> SpellChecker {
+        SpellChecker::new(self.tokenizer.snapshot())
+            .max_distance(max_distance)
+            .max_suggestions(max_suggestions)
+    }
+
+    /// A romanizer with a snapshot of the dictionary,
+    /// and exceptions for its engine.
+    fn romanizer(&self, engine: Engine, exceptions: HashMap<String, String>) -> Romanizer {
+        Romanizer::new(self.tokenizer.snapshot())
+            .engine(engine)
+            .exceptions(engine, exceptions)
     }
 }
-*/
 
 #[pymodule]
 fn _nlpo3_python_backend(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(load_dict, m)?)?;
-    m.add_function(wrap_pyfunction!(segment, m)?)?;
+    m.add_class::<PyNewmmTokenizer>()?;
     Ok(())
 }
@@ -0,0 +1,60 @@
+# SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+# SPDX-License-Identifier: Apache-2.0
+
+import unittest
+
+from nlpo3 import NewmmTokenizer
+from nlpo3.hf_tokenizers import NewmmPreTokenizer
+
+try:
+    import tokenizers
+except ImportError:
+    tokenizers = None
+
+
+class FakeNormalizedString:
+    # Stands in for tokenizers.NormalizedString, sliced by character index
+    def __init__(self, normalized: str, start: int = 0) -> None:
+        self.normalized = normalized
+        self.start = start
+
+    def __getitem__(self, key: slice) -> "FakeNormalizedString":
+        return FakeNormalizedString(
+            self.normalized[key], self.start + key.start
+        )
+
+
+class TestHfTokenizers(unittest.TestCase):
+    def setUp(self):
+        self.tokenizer = NewmmTokenizer.from_word_list(["กิน", "ข้าว", "ปลา"])
+
+    def test_split(self):
+        splits = NewmmPreTokenizer(self.tokenizer).split(
+            0, FakeNormalizedString("กินข้าว กินปลา")
+        )
+        self.assertEqual(
+            [(split.normalized, split.start) for split in splits],
+            [("กิน", 0), ("ข้าว", 3), ("กิน", 8), ("ปลา", 11)],
+        )
+
+        pre_tokenizer = NewmmPreTokenizer(self.tokenizer, keep_whitespace=True)
+        splits = pre_tokenizer.split(0, FakeNormalizedString("กิน ข้าว"))
+        self.assertEqual(
+            [(split.normalized, split.start) for split in splits],
+            [("กิน", 0), (" ", 3), ("ข้าว", 4)],
+        )
+
+    @unittest.skipIf(tokenizers is None, "tokenizers is not installed")
+    def test_pre_tokenizer_custom(self):
+        from tokenizers.pre_tokenizers import PreTokenizer
+
+        pre_tokenizer = PreTokenizer.custom(NewmmPreTokenizer(self.tokenizer))
+        self.assertEqual(
+            pre_tokenizer.pre_tokenize_str("กินข้าว กินปลา"),
+            [
+                ("กิน", (0, 3)),
+                ("ข้าว", (3, 7)),
+                ("กิน", (8, 11)),
+                ("ปลา", (11, 14)),
+            ],
+        )
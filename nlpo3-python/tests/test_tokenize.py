@@ -1,10 +1,22 @@
 # SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 # SPDX-License-Identifier: Apache-2.0
 
+import gzip
+import os
+import tempfile
 import unittest
+from concurrent.futures import ThreadPoolExecutor
 from typing import List
 
-from nlpo3 import load_dict, segment
+from nlpo3 import (
+    NewmmTokenizer,
+    add_word,
+    load_dict,
+    load_dict_from_words,
+    remove_word,
+    segment,
+    segment_batch,
+)
 
 
 class TestTokenizePackage(unittest.TestCase):
@@ -201,3 +213,147 @@ class TestTokenizePackage(unittest.TestCase):
         self.assertIsInstance(segment(self.DANGER_TEXT_1, DICT_NAME), List)
         self.assertIsInstance(segment(self.DANGER_TEXT_2, DICT_NAME), List)
         self.assertIsInstance(segment(self.DANGER_TEXT_3, DICT_NAME), List)
+
+    def test_newmm_tokenizer(self):
+        tokenizer = NewmmTokenizer("data/test_dict.txt")
+        self.assertEqual(tokenizer.segment(""), [])
+        self.assertEqual(tokenizer.segment("ไข่คน2021"), ["ไข่", "คน", "2021"])
+        self.assertEqual(
+            tokenizer.segment("ไข่คน2021", safe=True, parallel=True),
+            ["ไข่", "คน", "2021"],
+        )
+        self.assertIsInstance(tokenizer.segment(self.LONG_TEXT), List)
+
+        tokenizer.add_word(["ไข่คน"])
+        self.assertEqual(tokenizer.segment("ไข่คน2021"), ["ไข่คน", "2021"])
+        tokenizer.remove_word(["ไข่คน"])
+        self.assertEqual(tokenizer.segment("ไข่คน2021"), ["ไข่", "คน", "2021"])
+
+        with self.assertRaises(OSError):
+            NewmmTokenizer("data/no_such_dict.txt")
+        with self.assertRaises(OSError):
+            NewmmTokenizer("data")
+        with tempfile.TemporaryDirectory() as tmp_dir:
+            invalid_path = os.path.join(tmp_dir, "invalid_dict.txt")
+            with open(invalid_path, "wb") as dict_file:
+                dict_file.write(b"\xff\n")
+            with self.assertRaises(ValueError):
+                NewmmTokenizer(invalid_path)
+
+    def test_newmm_tokenizer_from_word_list(self):
+        tokenizer = NewmmTokenizer.from_word_list(["กิน", "ข้าว"])
+        self.assertEqual(tokenizer.segment("กินข้าว"), ["กิน", "ข้าว"])
+        # each tokenizer owns its dictionary
+        other = NewmmTokenizer.from_word_list(["กินข้าว"])
+        self.assertEqual(other.segment("กินข้าว"), ["กินข้าว"])
+        self.assertEqual(tokenizer.segment("กินข้าว"), ["กิน", "ข้าว"])
+
+    def test_newmm_tokenizer_from_bytes(self):
+        with open("data/test_dict.txt", "rb") as dict_file:
+            data = dict_file.read()
+        for dict_bytes in (data, gzip.compress(data)):
+            tokenizer = NewmmTokenizer.from_bytes(dict_bytes)
+            self.assertEqual(
+                tokenizer.segment("ไข่คน2021"), ["ไข่", "คน", "2021"]
+            )
+        with self.assertRaises(ValueError):
+            NewmmTokenizer.from_bytes(b"\xff\n")
+        with self.assertRaises(TypeError):
+            NewmmTokenizer.from_bytes("ไข่")
+
+    def test_dictionary_queries(self):
+        tokenizer = NewmmTokenizer.from_word_list(["กิน", "กินข้าว", "ข้าว"])
+        self.assertTrue(tokenizer.contains_word("กิน"))
+        self.assertFalse(tokenizer.contains_word("กา"))
+        self.assertEqual(
+            tokenizer.words_with_prefix("กิน"), ["กิน", "กินข้าว"]
+        )
+        self.assertEqual(
+            tokenizer.words_with_prefix("กิน", limit=1, order="frequency"),
+            ["กิน"],
+        )
+        self.assertEqual(tokenizer.words(), ["กิน", "กินข้าว", "ข้าว"])
+        with self.assertRaises(ValueError):
+            tokenizer.words_with_prefix("กิน", order="length")
+
+    def test_spelling(self):
+        tokenizer = NewmmTokenizer.from_bytes(
+            "กิน\nไป\nเที่ยว\nศาลา\t100\nสาลี\t80\nไก่\n".encode()
+        )
+        self.assertEqual(tokenizer.suggest_spelling("สาลา"), ["ศาลา", "สาลี"])
+        self.assertEqual(
+            tokenizer.suggest_spelling("สาลา", max_suggestions=1), ["ศาลา"]
+        )
+        self.assertEqual(
+            tokenizer.check_spelling("ไปเที่ยวสาลา"),
+            [(8, 12, "สาลา", ["ศาลา", "สาลี"])],
+        )
+        self.assertEqual(
+            tokenizer.correct_spelling("ไปเที่ยวสาลา กินไก้"),
+            "ไปเที่ยวศาลา กินไก่",
+        )
+
+    def test_romanize(self):
+        tokenizer = NewmmTokenizer.from_word_list(
+            ["ภาษา", "ไทย", "ข้าว", "ผัด", "มหาวิทยาลัย"]
+        )
+        self.assertEqual(tokenizer.romanize("ภาษาไทย"), "phasa thai")
+        self.assertEqual(tokenizer.romanize("ข้าว", engine="ipa"), "kʰaːw˥˩")
+        self.assertEqual(tokenizer.slug("ข้าวผัด 2 จาน!"), "khao-phat-2-chan")
+        exceptions = {"มหาวิทยาลัย": "mahawitthayalai"}
+        self.assertEqual(
+            tokenizer.slug("มหาวิทยาลัยไทย", exceptions=exceptions),
+            "mahawitthayalai-thai",
+        )
+        with self.assertRaises(ValueError):
+            tokenizer.romanize("ไทย", engine="royin")
+
+    def test_segment_unknown_dict_name(self):
+        with self.assertRaises(RuntimeError):
+            segment("ไข่คน", "no_such_dict_name")
+
+    def test_segment_batch(self):
+        DICT_NAME = "test_dict_batch"
+        load_dict("data/test_dict.txt", DICT_NAME)
+        texts = ["ไข่คน2021", "", self.LONG_TEXT, self.DANGER_TEXT_1]
+        self.assertEqual(
+            segment_batch(texts, DICT_NAME, safe=True),
+            [segment(text, DICT_NAME, safe=True) for text in texts],
+        )
+
+    def test_newmm_tokenizer_threads(self):
+        tokenizer = NewmmTokenizer("data/test_dict.txt")
+        texts = [self.LONG_TEXT, self.TEXT_1, self.DANGER_TEXT_2] * 4
+        with ThreadPoolExecutor(max_workers=4) as executor:
+            results = list(executor.map(tokenizer.segment, texts))
+        self.assertEqual(results, [tokenizer.segment(text) for text in texts])
+
+    def test_load_dict_from_words(self):
+        DICT_NAME = "test_dict_from_words"
+        self.assertTrue(load_dict_from_words(["กิน", "ข้าว"], DICT_NAME)[1])
+        self.assertFalse(load_dict_from_words(["กิน"], DICT_NAME)[1])
+        self.assertEqual(segment("กินข้าว", DICT_NAME), ["กิน", "ข้าว"])
+
+        # any iterable of str
+        words = (word for word in ["กิน", "ข้าว"])
+        tokenizer = NewmmTokenizer.from_word_list(words)
+        self.assertEqual(tokenizer.segment("กินข้าว"), ["กิน", "ข้าว"])
+        with self.assertRaises(TypeError):
+            NewmmTokenizer.from_word_list("กินข้าว")
+        with self.assertRaises(TypeError):
+            NewmmTokenizer.from_word_list(["กิน", 1])
+
+    def test_add_and_remove_word(self):
+        DICT_NAME = "test_dict_add_remove"
+        load_dict("data/test_dict.txt", DICT_NAME)
+        self.assertTrue(add_word({"ไข่คน"}, DICT_NAME)[1])
+        self.assertEqual(segment("ไข่คน2021", DICT_NAME), ["ไข่คน", "2021"])
+        self.assertTrue(remove_word(("ไข่คน",), DICT_NAME)[1])
+        self.assertEqual(
+            segment("ไข่คน2021", DICT_NAME), ["ไข่", "คน", "2021"]
+        )
+
+        self.assertFalse(add_word(["ไข่คน"], "no_such_dict_name")[1])
+        self.assertFalse(remove_word(["ไข่คน"], "no_such_dict_name")[1])
+        with self.assertRaises(TypeError):
+            add_word("ไข่คน", DICT_NAME)
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * WebAssembly binding for nlpO3, an natural language process library.
+ *
+ * Provides a tokenizer. Dictionaries are loaded from bytes,
+ * for example from a fetch() response, as there is no file system.
+ */
+use nlpo3::tokenizer::newmm;
+use nlpo3::tokenizer::tokenizer_trait::Tokenizer;
+use nlpo3::tokenizer::WordOrder;
+use wasm_bindgen::prelude::*;
+
+/// Dictionary-based maximal matching word tokenizer.
+#[wasm_bindgen]
+pub struct NewmmTokenizer {
+    tokenizer: newmm::NewmmTokenizer,
+}
+
+#[wasm_bindgen]
+impl NewmmTokenizer {
+    /// Create a tokenizer using the bytes of a dictionary file,
+    /// one word per line, in UTF-8.
+    /// Throws an Error if the bytes are not valid UTF-8.
+    #[wasm_bindgen(constructor)]
+    pub fn new(dict_bytes: &[u8]) -> Result<NewmmTokenizer, JsError> {
+        let tokenizer = newmm::NewmmTokenizer::from_bytes(dict_bytes)
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        Ok(NewmmTokenizer { tokenizer })
+    }
+
+    /// Create a tokenizer using an array of words.
+    #[wasm_bindgen(js_name = fromWordList)]
+    pub fn from_word_list(words: Vec<String>) -> NewmmTokenizer {
+        NewmmTokenizer {
+            tokenizer: newmm::NewmmTokenizer::from_word_list(words),
+        }
+    }
+
+    /// Break text into tokens.
+    /// Use safe mode to avoid long run times on text
+    /// with many ambiguous word boundaries.
+    pub fn segment(&self, text: &str, safe: Option<bool>) -> Result<Vec<String>, JsError> {
+        self.tokenizer
+            .segment(text, safe.unwrap_or(false), false)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    /// Add words to the dictionary.
+    #[wasm_bindgen(js_name = addWord)]
+    pub fn add_word(&mut self, words: Vec<String>) {
+        let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+        self.tokenizer.add_word(&words);
+    }
+
+    /// Remove words from the dictionary.
+    #[wasm_bindgen(js_name = removeWord)]
+    pub fn remove_word(&mut self, words: Vec<String>) {
+        let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+        self.tokenizer.remove_word(&words);
+    }
+
+    /// Check whether a word is in the dictionary.
+    #[wasm_bindgen(js_name = containsWord)]
+    pub fn contains_word(&self, word: &str) -> bool {
+        self.tokenizer.contains_word(word)
+    }
+
+    /// Find dictionary words starting with a prefix, at most `limit` of them,
+    /// in Unicode code point order, or highest frequency first.
+    #[wasm_bindgen(js_name = wordsWithPrefix)]
+    pub fn words_with_prefix(
+        &self,
+        prefix: &str,
+        limit: Option<usize>,
+        by_frequency: Option<bool>,
+    ) -> Vec<String> {
+        let order = if by_frequency.unwrap_or(false) {
+            WordOrder::Frequency
+        } else {
+            WordOrder::Alphabetical
+        };
+        self.tokenizer.words_with_prefix(prefix, limit, order)
+    }
+
+    /// List all dictionary words, in Unicode code point order.
+    pub fn words(&self) -> Vec<String> {
+        self.tokenizer.words().collect()
+    }
+}
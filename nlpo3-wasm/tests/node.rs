@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Test the WebAssembly binding with Node.js, see README.md.
+ */
+#![cfg(target_arch = "wasm32")]
+
+use nlpo3_wasm::NewmmTokenizer;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn test_segment() {
+    let tokenizer = NewmmTokenizer::new("กิน\nข้าว\n".as_bytes()).unwrap();
+    assert_eq!(
+        tokenizer.segment("กินข้าว abc", None).unwrap(),
+        ["กิน", "ข้าว", " ", "abc"]
+    );
+    assert_eq!(
+        tokenizer.segment("กินข้าว", Some(true)).unwrap(),
+        ["กิน", "ข้าว"]
+    );
+    assert!(NewmmTokenizer::new(&[0xff]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_add_and_remove_word() {
+    let mut tokenizer = NewmmTokenizer::from_word_list(vec!["กิน".to_string(), "ข้าว".to_string()]);
+    tokenizer.add_word(vec!["กินข้าว".to_string()]);
+    assert_eq!(tokenizer.segment("กินข้าว", None).unwrap(), ["กินข้าว"]);
+    tokenizer.remove_word(vec!["กินข้าว".to_string()]);
+    assert_eq!(tokenizer.segment("กินข้าว", None).unwrap(), ["กิน", "ข้าว"]);
+}
+
+#[wasm_bindgen_test]
+fn test_dictionary_queries() {
+    let tokenizer = NewmmTokenizer::new("กิน\t10\nกินข้าว\t20\nข้าว\n".as_bytes()).unwrap();
+    assert!(tokenizer.contains_word("กิน"));
+    assert_eq!(
+        tokenizer.words_with_prefix("กิน", None, None),
+        ["กิน", "กินข้าว"]
+    );
+    assert_eq!(
+        tokenizer.words_with_prefix("กิน", Some(1), Some(true)),
+        ["กินข้าว"]
+    );
+    assert_eq!(tokenizer.words(), ["กิน", "กินข้าว", "ข้าว"]);
+}
@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Word frequency counting and corpus statistics.
+ *
+ * Whitespace tokens are not counted, and a bigram never spans across one.
+ * Out-of-dictionary (OOV) words are the Thai tokens
+ * that went through the unknown-word path of the tokenizer.
+*/
+use std::hash::Hash;
+
+use crate::tokenizer::{
+    newmm::NewmmTokenizer,
+    token::{Token, TokenKind},
+};
+use anyhow::Result as AnyResult;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+#[derive(Clone, Debug, Default)]
+pub struct CorpusStats {
+    /// Number of documents
+    pub documents: usize,
+    /// Number of counted (non-whitespace) tokens
+    pub tokens: usize,
+    pub unigrams: HashMap<String, usize>,
+    pub bigrams: HashMap<(String, String), usize>,
+    /// Number of documents each word occurs in
+    pub document_frequencies: HashMap<String, usize>,
+    /// Out-of-dictionary words and their counts
+    pub oov: HashMap<String, usize>,
+}
+
+impl CorpusStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count the tokens of one document.
+    pub fn add_tokens(&mut self, tokens: &[Token]) {
+        self.documents += 1;
+        let mut words_in_document: HashSet<&str> = HashSet::default();
+        let mut previous: Option<&str> = None;
+        for token in tokens {
+            if token.text.trim().is_empty() {
+                previous = None;
+                continue;
+            }
+            self.tokens += 1;
+            *self.unigrams.entry(token.text.clone()).or_insert(0) += 1;
+            if token.kind == TokenKind::Unknown {
+                *self.oov.entry(token.text.clone()).or_insert(0) += 1;
+            }
+            if let Some(previous) = previous {
+                *self
+                    .bigrams
+                    .entry((previous.to_string(), token.text.clone()))
+                    .or_insert(0) += 1;
+            }
+            words_in_document.insert(&token.text);
+            previous = Some(&token.text);
+        }
+        for word in words_in_document {
+            *self
+                .document_frequencies
+                .entry(word.to_string())
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Segment a document with `tokenizer` and count its tokens.
+    pub fn add_document(
+        &mut self,
+        tokenizer: &NewmmTokenizer,
+        text: &str,
+        safe: bool,
+    ) -> AnyResult<()> {
+        let tokens = tokenizer.segment_to_tokens(text, safe, false)?;
+        self.add_tokens(&tokens);
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.documents += other.documents;
+        self.tokens += other.tokens;
+        merge_counts(&mut self.unigrams, &other.unigrams);
+        merge_counts(&mut self.bigrams, &other.bigrams);
+        merge_counts(&mut self.document_frequencies, &other.document_frequencies);
+        merge_counts(&mut self.oov, &other.oov);
+    }
+}
+
+fn merge_counts<K: Clone + Eq + Hash>(counts: &mut HashMap<K, usize>, other: &HashMap<K, usize>) {
+    for (key, count) in other.iter() {
+        *counts.entry(key.clone()).or_insert(0) += count;
+    }
+}
+
+/// Returns the entries with a count of at least `min_count`,
+/// most frequent first, up to `top_k` entries (all entries if `top_k` is `None`).
+/// Ties are ordered by key.
+pub fn top_k<K: Ord>(
+    counts: &HashMap<K, usize>,
+    top_k: Option<usize>,
+    min_count: usize,
+) -> Vec<(&K, usize)> {
+    let mut entries: Vec<(&K, usize)> = counts
+        .iter()
+        .filter(|(_, count)| **count >= min_count)
+        .map(|(key, count)| (key, *count))
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    if let Some(limit) = top_k {
+        entries.truncate(limit);
+    }
+    entries
+}
+
+#[test]
+fn test_corpus_stats() {
+    let tokenizer = NewmmTokenizer::from_word_list(vec![
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+        "ปลา".to_string(),
+    ]);
+    let mut stats = CorpusStats::new();
+    stats
+        .add_document(&tokenizer, "กินข้าว กินปลา", false)
+        .unwrap();
+    stats.add_document(&tokenizer, "กินสตู", false).unwrap();
+
+    assert_eq!(stats.documents, 2);
+    assert_eq!(stats.tokens, 6);
+    assert_eq!(
+        top_k(&stats.unigrams, Some(2), 1),
+        [(&"กิน".to_string(), 3), (&"ข้าว".to_string(), 1)]
+    );
+    assert_eq!(top_k(&stats.unigrams, None, 2).len(), 1);
+    // no bigram across the space
+    assert_eq!(stats.bigrams.len(), 3);
+    assert_eq!(stats.bigrams[&("กิน".to_string(), "ข้าว".to_string())], 1);
+    assert_eq!(stats.document_frequencies["กิน"], 2);
+    assert_eq!(stats.document_frequencies["ปลา"], 1);
+    assert_eq!(top_k(&stats.oov, None, 1), [(&"สตู".to_string(), 1)]);
+}
@@ -0,0 +1,384 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Evaluation of word segmentation against gold-segmented text.
+ *
+ * Gold text uses the BEST-2010 style: one sentence per line,
+ * words delimited by "|". Named entity, abbreviation, and poem tags
+ * (<NE>, <AB>, <POEM>) are ignored.
+ *
+ * Two scores are computed:
+ *  * Character boundary: each position between two characters
+ *    where a word starts is a boundary.
+ *  * Word: a word is correct when both its start and end match.
+*/
+use std::{error::Error, fmt::Display};
+
+use crate::tokenizer::newmm::NewmmTokenizer;
+use crate::tokenizer::tokenizer_trait::Tokenizer;
+use anyhow::Result as AnyResult;
+use rustc_hash::FxHashMap as HashMap;
+
+const GOLD_DELIMITER: char = '|';
+const GOLD_TAGS: &[&str] = &["<NE>", "</NE>", "<AB>", "</AB>", "<POEM>", "</POEM>"];
+
+#[derive(Clone, Debug)]
+pub struct TextMismatchError {
+    gold: String,
+    predicted: String,
+}
+
+impl Display for TextMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Segmented text {:?} does not match gold text {:?}",
+            self.predicted, self.gold
+        )
+    }
+}
+
+impl Error for TextMismatchError {}
+
+/// Counts of correct, predicted, and gold items.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub correct: usize,
+    pub predicted: usize,
+    pub gold: usize,
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        1.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+impl Counts {
+    pub fn precision(&self) -> f64 {
+        ratio(self.correct, self.predicted)
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(self.correct, self.gold)
+    }
+
+    pub fn f1(&self) -> f64 {
+        let precision = self.precision();
+        let recall = self.recall();
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.correct += other.correct;
+        self.predicted += other.predicted;
+        self.gold += other.gold;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ErrorKind {
+    /// One gold word was split into several tokens
+    OverSegmentation,
+    /// Several gold words were merged into one token
+    UnderSegmentation,
+    /// Gold words and tokens overlap without sharing boundaries
+    Mixed,
+}
+
+/// A segmentation error: the words of a text span in the gold text
+/// and in the tokenizer output, each joined with "|".
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorPattern {
+    pub kind: ErrorKind,
+    pub gold: String,
+    pub predicted: String,
+}
+
+/// Evaluation result of one or more segmented texts.
+#[derive(Clone, Debug, Default)]
+pub struct EvalResult {
+    /// Number of evaluated texts (lines)
+    pub texts: usize,
+    pub char_boundary: Counts,
+    pub word: Counts,
+    /// How many times each error pattern occurs
+    pub errors: HashMap<ErrorPattern, usize>,
+}
+
+impl EvalResult {
+    pub fn merge(&mut self, other: &Self) {
+        self.texts += other.texts;
+        self.char_boundary.merge(&other.char_boundary);
+        self.word.merge(&other.word);
+        for (pattern, count) in other.errors.iter() {
+            *self.errors.entry(pattern.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Returns up to `limit` most frequent error patterns, most frequent first.
+    pub fn top_errors(&self, limit: usize) -> Vec<(&ErrorPattern, usize)> {
+        let mut errors: Vec<(&ErrorPattern, usize)> = self
+            .errors
+            .iter()
+            .map(|(pattern, count)| (pattern, *count))
+            .collect();
+        errors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        errors.truncate(limit);
+        errors
+    }
+}
+
+/// Split a gold-segmented line into words.
+pub fn parse_gold_line(line: &str) -> Vec<String> {
+    let mut line = line.trim_end_matches(&['\r', '\n'][..]).to_string();
+    for tag in GOLD_TAGS {
+        line = line.replace(tag, "");
+    }
+    line.split(GOLD_DELIMITER)
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// End positions (character indices) of each word.
+fn word_ends<S: AsRef<str>>(words: &[S]) -> Vec<usize> {
+    let mut position = 0;
+    words
+        .iter()
+        .map(|word| {
+            position += word.as_ref().chars().count();
+            position
+        })
+        .collect()
+}
+
+/// Compare the tokens of one text with its gold words.
+pub fn evaluate_words<G: AsRef<str>, P: AsRef<str>>(
+    gold: &[G],
+    predicted: &[P],
+) -> AnyResult<EvalResult> {
+    let gold_text: String = gold.iter().map(|word| word.as_ref()).collect();
+    let predicted_text: String = predicted.iter().map(|word| word.as_ref()).collect();
+    if gold_text != predicted_text {
+        return Err(TextMismatchError {
+            gold: gold_text,
+            predicted: predicted_text,
+        }
+        .into());
+    }
+    let gold_ends = word_ends(gold);
+    let predicted_ends = word_ends(predicted);
+
+    let mut result = EvalResult {
+        texts: 1,
+        ..Default::default()
+    };
+    // the end of the text is not a boundary
+    result.char_boundary.gold = gold_ends.len().saturating_sub(1);
+    result.char_boundary.predicted = predicted_ends.len().saturating_sub(1);
+    result.word.gold = gold_ends.len();
+    result.word.predicted = predicted_ends.len();
+
+    // walk both lists of word ends, grouping words into spans
+    // that start and end at shared boundaries
+    let (mut gold_index, mut predicted_index) = (0, 0);
+    let (mut gold_span_start, mut predicted_span_start) = (0, 0);
+    while gold_index < gold_ends.len() && predicted_index < predicted_ends.len() {
+        let gold_end = gold_ends[gold_index];
+        let predicted_end = predicted_ends[predicted_index];
+        if gold_end < predicted_end {
+            gold_index += 1;
+            continue;
+        }
+        if predicted_end < gold_end {
+            predicted_index += 1;
+            continue;
+        }
+
+        gold_index += 1;
+        predicted_index += 1;
+        if gold_index < gold_ends.len() {
+            result.char_boundary.correct += 1;
+        }
+        let gold_words = &gold[gold_span_start..gold_index];
+        let predicted_words = &predicted[predicted_span_start..predicted_index];
+        if gold_words.len() == 1 && predicted_words.len() == 1 {
+            result.word.correct += 1;
+        } else {
+            let kind = match (gold_words.len(), predicted_words.len()) {
+                (1, _) => ErrorKind::OverSegmentation,
+                (_, 1) => ErrorKind::UnderSegmentation,
+                _ => ErrorKind::Mixed,
+            };
+            let pattern = ErrorPattern {
+                kind,
+                gold: join_words(gold_words),
+                predicted: join_words(predicted_words),
+            };
+            *result.errors.entry(pattern).or_insert(0) += 1;
+        }
+        gold_span_start = gold_index;
+        predicted_span_start = predicted_index;
+    }
+    Ok(result)
+}
+
+fn join_words<S: AsRef<str>>(words: &[S]) -> String {
+    words
+        .iter()
+        .map(|word| word.as_ref())
+        .collect::<Vec<&str>>()
+        .join("|")
+}
+
+/// Compare the words of each gold line with the words found by `segment`.
+/// Empty lines are skipped.
+fn evaluate_lines<'a, I, F>(gold_lines: I, mut segment: F) -> AnyResult<EvalResult>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: FnMut(&str) -> AnyResult<Vec<String>>,
+{
+    let mut result = EvalResult::default();
+    for line in gold_lines {
+        let gold = parse_gold_line(line);
+        if gold.is_empty() {
+            continue;
+        }
+        let text: String = gold.concat();
+        let predicted = segment(&text)?;
+        result.merge(&evaluate_words(&gold, &predicted)?);
+    }
+    Ok(result)
+}
+
+/// Segment each gold line with `tokenizer` and compare the result with the gold words.
+/// Empty lines are skipped.
+pub fn evaluate<'a, T, I>(tokenizer: &T, gold_lines: I, safe: bool) -> AnyResult<EvalResult>
+where
+    T: Tokenizer + ?Sized,
+    I: IntoIterator<Item = &'a str>,
+{
+    evaluate_lines(gold_lines, |text| tokenizer.segment(text, safe, false))
+}
+
+/// Like `evaluate`, but the predicted words are the spans of the tokens
+/// in the gold text, so a tokenizer with a normalizer is evaluated
+/// against the text as it is, not against the normalized text.
+pub fn evaluate_spans<'a, I>(
+    tokenizer: &NewmmTokenizer,
+    gold_lines: I,
+    safe: bool,
+) -> AnyResult<EvalResult>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    evaluate_lines(gold_lines, |text| {
+        Ok(tokenizer
+            .segment_to_byte_spans(text, safe, false)?
+            .into_iter()
+            .map(|(_, span)| &text[span])
+            // characters rewritten together leave empty spans
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_string())
+            .collect())
+    })
+}
+
+#[test]
+fn test_parse_gold_line() {
+    assert_eq!(
+        parse_gold_line("<NE>สมชาย</NE>|กิน|ข้าว| |<AB>กทม.</AB>|\n"),
+        ["สมชาย", "กิน", "ข้าว", " ", "กทม."]
+    );
+    assert!(parse_gold_line("\n").is_empty());
+}
+
+#[test]
+fn test_evaluate_words() {
+    let gold = ["ไป", "กิน", "ข้าวผัด", "ที่", "บ้าน"];
+    let predicted = ["ไปกิน", "ข้าว", "ผัด", "ที่", "บ้าน"];
+    let result = evaluate_words(&gold, &predicted).unwrap();
+    // gold boundaries: 2, 5, 12, 15; predicted: 5, 9, 12, 15
+    assert_eq!(
+        result.char_boundary,
+        Counts {
+            correct: 3,
+            predicted: 4,
+            gold: 4
+        }
+    );
+    assert_eq!(
+        result.word,
+        Counts {
+            correct: 2,
+            predicted: 5,
+            gold: 5
+        }
+    );
+    assert_eq!(result.word.precision(), 0.4);
+    assert_eq!(
+        result.top_errors(10),
+        [
+            (
+                &ErrorPattern {
+                    kind: ErrorKind::OverSegmentation,
+                    gold: "ข้าวผัด".to_string(),
+                    predicted: "ข้าว|ผัด".to_string(),
+                },
+                1
+            ),
+            (
+                &ErrorPattern {
+                    kind: ErrorKind::UnderSegmentation,
+                    gold: "ไป|กิน".to_string(),
+                    predicted: "ไปกิน".to_string(),
+                },
+                1
+            ),
+        ]
+    );
+    assert!(evaluate_words(&gold, &["ไป"]).is_err());
+}
+
+#[test]
+fn test_evaluate_perfect_single_word() {
+    let result = evaluate_words(&["ข้าว"], &["ข้าว"]).unwrap();
+    assert_eq!(result.char_boundary.f1(), 1.0);
+    assert_eq!(result.word.f1(), 1.0);
+    assert!(result.errors.is_empty());
+}
+
+#[test]
+fn test_evaluate_with_normalizer() {
+    use crate::normalize::Normalizer;
+
+    let mut tokenizer = NewmmTokenizer::from_word_list(vec!["มาก".to_string(), "ไป".to_string()]);
+    tokenizer.set_normalizer(Some(Normalizer::new()));
+    // "มากกก" is normalized to "มาก", but is compared as it is in the gold text
+    let result = evaluate_spans(&tokenizer, vec!["ไป|มากกก|", "ไป|มาก|"], false).unwrap();
+    assert_eq!(result.texts, 2);
+    assert_eq!(result.word.f1(), 1.0);
+    // the segmented normalized text does not match the gold text
+    assert!(evaluate(&tokenizer, vec!["ไป|มากกก|"], false).is_err());
+}
+
+#[test]
+fn test_evaluate_any_tokenizer() {
+    use crate::tokenizer::shared::SharedNewmmTokenizer;
+
+    let tokenizer = SharedNewmmTokenizer::new(NewmmTokenizer::from_word_list(vec![
+        "ข้าว".to_string(),
+        "ผัด".to_string(),
+    ]));
+    let tokenizer: &dyn Tokenizer = &tokenizer;
+    let result = evaluate(tokenizer, vec!["ข้าว|ผัด|"], false).unwrap();
+    assert_eq!(result.word.f1(), 1.0);
+}
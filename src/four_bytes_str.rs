@@ -3,3 +3,5 @@
 
 pub mod custom_regex;
 pub mod custom_string;
+pub mod normalized_text;
+pub mod sanitize;
@@ -330,28 +330,27 @@ impl CustomString {
         self.chars_len() == 0
     }
 
+    /// Returns this string without leading and trailing whitespace.
     pub fn trim(&self) -> Self {
-        let mut new_content: &[u8] = &self.content;
+        let content = self.raw_content();
+        let mut start = 0;
+        let mut end = self.chars_len();
 
-        while !new_content.is_empty() && is_whitespace(&new_content[0..BYTES_PER_CHAR]) {
+        while start < end && is_whitespace(content.slice_by_char_indice(start, start + 1)) {
             // trim left
-            new_content = &new_content[BYTES_PER_CHAR..];
+            start += 1;
         }
 
-        while !new_content.is_empty()
-            && is_whitespace(&new_content[(new_content.len() - BYTES_PER_CHAR)..])
-        {
+        while end > start && is_whitespace(content.slice_by_char_indice(end - 1, end)) {
             // trim right
-            new_content = &new_content[..(new_content.len() - BYTES_PER_CHAR)];
+            end -= 1;
         }
 
-        let length = new_content.len() / BYTES_PER_CHAR;
-
         Self {
-            content: Arc::new(Vec::from(new_content)),
-            chars_content: self.chars_content.clone(),
+            content: Arc::new(Vec::from(content.slice_by_char_indice(start, end))),
+            chars_content: Arc::new(self.get_chars_content()[start..end].to_vec()),
             start: 0,
-            end: length,
+            end: end - start,
         }
     }
 
@@ -448,9 +447,9 @@ impl CustomString {
         }
     }
 
+    /// start and end are character index, relative to this (sub)string.
     pub fn substring_as_bytes(&self, char_start: usize, char_end: usize) -> &[u8] {
-        self.content
-            .as_slice()
+        self.raw_content()
             .slice_by_char_indice(char_start, char_end)
     }
 }
@@ -480,6 +479,16 @@ fn test_bytes() {
     assert_eq!(custom_string.full_string_bytes_len() % 4, 0);
 }
 
+#[test]
+fn test_substring_as_bytes() {
+    let text = CustomString::new("กขคง");
+    let substring = text.substring(1, 4);
+    assert_eq!(
+        substring.substring_as_bytes(0, 2),
+        CustomString::new("ขค").raw_content()
+    );
+}
+
 #[test]
 fn test_trim() {
     assert!(CustomString::new(" ").trim().is_empty());
@@ -488,4 +497,8 @@ fn test_trim() {
     assert!(CustomString::new("  \t\n ").trim().is_empty());
     assert_eq!(CustomString::new(" abc ").trim().chars_len(), 3);
     assert_eq!(CustomString::new(" aก  ").trim().full_string_bytes_len(), 8); // 2 chars * 4 bytes
+    let trimmed = CustomString::new(" aก  ").trim();
+    assert_eq!(trimmed.get_chars_content(), ['a', 'ก']);
+    let trimmed = CustomString::new(" aก ").substring(0, 2).trim();
+    assert_eq!(trimmed.get_chars_content(), ['a']);
 }
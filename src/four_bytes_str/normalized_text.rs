@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Text rewritten character by character, such as a sanitized or a normalized
+ * text, with the position of each character in the original text.
+*/
+
+/// A character and the index of the first original character it comes from.
+pub(crate) type Indexed = (char, usize);
+
+/// A normalized text, with the positions of its characters in the original text.
+///
+/// Positions are character (Unicode scalar value) indices.
+/// Each normalized character covers a span of original characters;
+/// the spans follow each other without gaps, and cover removed characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NormalizedText {
+    text: String,
+    /// offsets[i] is where the span of the i-th character starts,
+    /// with the length of the original text at the end
+    offsets: Vec<usize>,
+}
+
+impl NormalizedText {
+    /// Text of characters, each with the index of the first original
+    /// character it comes from, in order.
+    pub(crate) fn from_indexed(chars: &[Indexed], original_len: usize) -> Self {
+        let mut offsets: Vec<usize> = chars.iter().map(|&(_, index)| index).collect();
+        // removed characters belong to the character before them,
+        // or to the first character if they start the text
+        if let Some(first) = offsets.first_mut() {
+            *first = 0;
+        }
+        offsets.push(original_len);
+        Self {
+            text: chars.iter().map(|&(character, _)| character).collect(),
+            offsets,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Number of characters of the normalized text
+    pub fn chars_len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Map a span `start..end` of the normalized text to the original text.
+    ///
+    /// When characters are rewritten together, such as a tone mark moved after
+    /// a vowel, the last one covers the original span of the group
+    /// and the others cover an empty span.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is greater than the number of characters.
+    pub fn original_span(&self, start: usize, end: usize) -> (usize, usize) {
+        (self.original_position(start), self.original_position(end))
+    }
+
+    /// Map a position between two characters of the normalized text
+    /// to the original text, like the ends of `original_span`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is greater than the number of characters.
+    pub fn original_position(&self, position: usize) -> usize {
+        self.offsets[position]
+    }
+}
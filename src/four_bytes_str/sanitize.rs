@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Input sanitation before text is converted to a custom string.
+ *
+ * `CustomString::new` keeps every character of its input, so the same
+ * visible text can give different custom strings, and different tokens:
+ * with a zero-width space or a soft hyphen in a word, with a byte order mark,
+ * or with an accented letter typed as a letter and a combining mark.
+ *
+ * A `Sanitizer` removes invisible characters and normalizes the text to
+ * a Unicode normalization form, keeping the position of each character
+ * in the original text. Characters are normalized in groups, each group
+ * a starter and the marks combining with it, so a group changed by the
+ * normalization maps to its whole original span.
+ *
+ * Custom strings are internal, so there is no `CustomString` constructor
+ * taking a sanitizer: a tokenizer sanitizes its input through a `Normalizer`
+ * with a sanitizer, see `NewmmTokenizer::set_normalizer`.
+ * `Sanitizer::sanitize` can also be used on its own.
+ *
+ * NFKC also folds compatibility characters, such as fullwidth Latin letters,
+ * but it keeps sara am (ำ), which it would decompose into nikhahit and sara aa.
+*/
+use std::iter::once;
+
+use super::normalized_text::{Indexed, NormalizedText};
+use unicode_normalization::{
+    char::canonical_combining_class, is_nfc_quick, is_nfkc_quick, IsNormalized,
+    UnicodeNormalization,
+};
+
+const SARA_AM: char = '\u{0E33}';
+const LAO_AM: char = '\u{0EB3}';
+
+/// Zero-width space, non-joiner and joiner, word joiner, and byte order mark.
+pub(crate) fn is_zero_width(character: char) -> bool {
+    matches!(
+        character,
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+    )
+}
+
+/// Zero-width characters, soft hyphen, combining grapheme joiner,
+/// bidirectional marks and controls, and invisible operators.
+pub(crate) fn is_invisible(character: char) -> bool {
+    is_zero_width(character)
+        || matches!(
+            character,
+            '\u{00AD}'
+                | '\u{034F}'
+                | '\u{061C}'
+                | '\u{180E}'
+                | '\u{200E}'
+                | '\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2061}'..='\u{2064}'
+                | '\u{2066}'..='\u{206F}'
+        )
+}
+
+/// A Unicode normalization form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum UnicodeForm {
+    /// Canonical composition
+    Nfc,
+    /// Compatibility composition
+    Nfkc,
+}
+
+impl UnicodeForm {
+    /// Whether a group of characters normalized together can start here:
+    /// a starter that does not compose with the character before it.
+    fn is_group_start(self, character: char) -> bool {
+        let quick_check = match self {
+            UnicodeForm::Nfc => is_nfc_quick(once(character)),
+            UnicodeForm::Nfkc => is_nfkc_quick(once(character)),
+        };
+        canonical_combining_class(character) == 0 && quick_check != IsNormalized::Maybe
+    }
+
+    fn normalize(self, group: &[char]) -> Vec<char> {
+        match self {
+            UnicodeForm::Nfc => group.iter().copied().nfc().collect(),
+            UnicodeForm::Nfkc if group.contains(&SARA_AM) || group.contains(&LAO_AM) => {
+                group.to_vec()
+            }
+            UnicodeForm::Nfkc => group.iter().copied().nfkc().collect(),
+        }
+    }
+}
+
+/// A sanitizer removing invisible characters and normalizing to NFC by default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Sanitizer {
+    form: Option<UnicodeForm>,
+    strip_invisible: bool,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sanitizer {
+    pub fn new() -> Self {
+        Self {
+            form: Some(UnicodeForm::Nfc),
+            strip_invisible: true,
+        }
+    }
+
+    /// Set the normalization form, or keep the text as is with `None`
+    /// (default: NFC).
+    pub fn form(mut self, form: Option<UnicodeForm>) -> Self {
+        self.form = form;
+        self
+    }
+
+    /// Remove zero-width characters, soft hyphens, byte order marks,
+    /// bidirectional marks and other invisible characters (default: true).
+    pub fn strip_invisible(mut self, enabled: bool) -> Self {
+        self.strip_invisible = enabled;
+        self
+    }
+
+    /// Sanitize a text, keeping the positions of its characters.
+    pub fn sanitize(&self, text: &str) -> NormalizedText {
+        NormalizedText::from_indexed(&self.sanitize_indexed(text), text.chars().count())
+    }
+
+    /// Sanitized characters, each with the index of the first original
+    /// character it comes from.
+    pub(crate) fn sanitize_indexed(&self, text: &str) -> Vec<Indexed> {
+        let mut chars: Vec<Indexed> = text.chars().zip(0..).collect();
+        if self.strip_invisible {
+            chars.retain(|&(character, _)| !is_invisible(character));
+        }
+        let form = match self.form {
+            Some(form) => form,
+            None => return chars,
+        };
+
+        let mut sanitized: Vec<Indexed> = Vec::with_capacity(chars.len());
+        let mut group_start = 0;
+        while group_start < chars.len() {
+            let group_len = 1 + chars[group_start + 1..]
+                .iter()
+                .take_while(|&&(character, _)| !form.is_group_start(character))
+                .count();
+            let group = &chars[group_start..group_start + group_len];
+            let original: Vec<char> = group.iter().map(|&(character, _)| character).collect();
+            let normalized = form.normalize(&original);
+            if normalized == original {
+                sanitized.extend(group);
+            } else {
+                // a changed group maps to its whole span
+                let index = group[0].1;
+                sanitized.extend(normalized.into_iter().map(|character| (character, index)));
+            }
+            group_start += group_len;
+        }
+        sanitized
+    }
+}
+
+#[test]
+fn test_strip_invisible() {
+    let sanitizer = Sanitizer::new();
+    assert_eq!(
+        sanitizer
+            .sanitize("\u{FEFF}คอม\u{00AD}พิว\u{200B}เตอร์\u{200E}")
+            .as_str(),
+        "คอมพิวเตอร์"
+    );
+    let sanitized = sanitizer.sanitize("ทำ\u{200B}งาน");
+    assert_eq!(sanitized.original_span(0, 2), (0, 3));
+    assert_eq!(sanitized.original_span(2, 5), (3, 6));
+
+    let sanitizer = Sanitizer::new().strip_invisible(false);
+    assert_eq!(
+        sanitizer.sanitize("ทำ\u{200B}งาน").as_str(),
+        "ทำ\u{200B}งาน"
+    );
+}
+
+#[test]
+fn test_unicode_forms() {
+    let sanitizer = Sanitizer::new();
+    let sanitized = sanitizer.sanitize("cafe\u{0301} ok");
+    assert_eq!(sanitized.as_str(), "caf\u{00E9} ok");
+    assert_eq!(sanitized.original_span(3, 4), (3, 5));
+    assert_eq!(sanitized.original_span(4, 7), (5, 8));
+    // Hangul jamo compose across starters
+    assert_eq!(sanitizer.sanitize("\u{1100}\u{1161}").as_str(), "\u{AC00}");
+    assert_eq!(sanitizer.sanitize("ＡＢ①").as_str(), "ＡＢ①");
+    // Thai text is already in NFC
+    assert_eq!(sanitizer.sanitize("น้ำใจ").as_str(), "น้ำใจ");
+
+    let sanitizer = Sanitizer::new().form(Some(UnicodeForm::Nfkc));
+    assert_eq!(sanitizer.sanitize("ＡＢ①ﬁ").as_str(), "AB1fi");
+    assert_eq!(sanitizer.sanitize("น้ำใจ").as_str(), "น้ำใจ");
+
+    let sanitizer = Sanitizer::new().form(None);
+    assert_eq!(sanitizer.sanitize("cafe\u{0301}").as_str(), "cafe\u{0301}");
+    let sanitized = Sanitizer::new().sanitize("e\u{0301}\u{200B}");
+    assert_eq!(sanitized.as_str(), "\u{00E9}");
+    assert_eq!(sanitized.original_span(0, 1), (0, 3));
+}
@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Pre-tokenizer for the Hugging Face tokenizers library.
+ *
+ * Splits text into Thai words before a subword model (BPE, WordPiece, Unigram),
+ * with `tokenizer.with_pre_tokenizer(Some(pre_tokenizer))`:
+ *
+ * ```
+ * use std::sync::Arc;
+ *
+ * use nlpo3::hf_tokenizers::NewmmPreTokenizer;
+ * use nlpo3::tokenizer::newmm::NewmmTokenizer;
+ * use tokenizers::{OffsetReferential, OffsetType, PreTokenizedString, PreTokenizer};
+ *
+ * let words = vec!["กิน".to_string(), "ข้าว".to_string()];
+ * let tokenizer = NewmmTokenizer::from_word_list(words);
+ * let pre_tokenizer = NewmmPreTokenizer::new(Arc::new(tokenizer));
+ * let mut pretokenized = PreTokenizedString::from("กินข้าว");
+ * pre_tokenizer.pre_tokenize(&mut pretokenized)?;
+ * let splits: Vec<&str> = pretokenized
+ *     .get_splits(OffsetReferential::Original, OffsetType::Byte)
+ *     .into_iter()
+ *     .map(|(split, _, _)| split)
+ *     .collect();
+ * assert_eq!(splits, ["กิน", "ข้าว"]);
+ * # Ok::<(), tokenizers::Error>(())
+ * ```
+ *
+ * Each split is a slice of the normalized string, so the offsets of
+ * the subword tokens map back to the original text.
+*/
+use std::sync::Arc;
+
+use crate::tokenizer::newmm::NewmmTokenizer;
+use tokenizers::normalizer::Range;
+use tokenizers::{NormalizedString, PreTokenizedString, PreTokenizer, Result};
+
+/// A `PreTokenizer` backed by a [shared](NewmmTokenizer#sharing) `NewmmTokenizer`.
+#[derive(Clone, Debug)]
+pub struct NewmmPreTokenizer {
+    tokenizer: Arc<NewmmTokenizer>,
+    safe: bool,
+    keep_whitespace: bool,
+}
+
+impl NewmmPreTokenizer {
+    /// Create a pre-tokenizer in safe mode, which drops whitespace splits.
+    pub fn new(tokenizer: Arc<NewmmTokenizer>) -> Self {
+        Self {
+            tokenizer,
+            safe: true,
+            keep_whitespace: false,
+        }
+    }
+
+    /// Set [safe mode](NewmmTokenizer#safe-mode) (default: true).
+    pub fn safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Keep whitespace and line break splits instead of dropping them (default: false).
+    pub fn keep_whitespace(mut self, keep_whitespace: bool) -> Self {
+        self.keep_whitespace = keep_whitespace;
+        self
+    }
+
+    /// Split a normalized string into words.
+    fn split(&self, normalized: NormalizedString) -> Result<Vec<NormalizedString>> {
+        let text = normalized.get();
+        let tokens = self
+            .tokenizer
+            .segment_to_byte_spans(text, self.safe, false)?;
+        Ok(tokens
+            .into_iter()
+            .filter(|(token, _)| self.keep_whitespace || !token.text.trim().is_empty())
+            .filter_map(|(_, span)| normalized.slice(Range::Normalized(span)))
+            .collect())
+    }
+}
+
+impl PreTokenizer for NewmmPreTokenizer {
+    fn pre_tokenize(&self, pretokenized: &mut PreTokenizedString) -> Result<()> {
+        pretokenized.split(|_, normalized| self.split(normalized))
+    }
+}
+
+#[cfg(test)]
+fn pre_tokenize(pre_tokenizer: &NewmmPreTokenizer, text: &str) -> Vec<(String, (usize, usize))> {
+    use tokenizers::{OffsetReferential, OffsetType};
+
+    let mut pretokenized = PreTokenizedString::from(text);
+    pre_tokenizer.pre_tokenize(&mut pretokenized).unwrap();
+    pretokenized
+        .get_splits(OffsetReferential::Original, OffsetType::Byte)
+        .into_iter()
+        .map(|(split, offsets, _)| (split.to_string(), offsets))
+        .collect()
+}
+
+#[test]
+fn test_pre_tokenize_offsets() {
+    let words = vec!["กิน".to_string(), "ข้าว".to_string(), "ปลา".to_string()];
+    let pre_tokenizer = NewmmPreTokenizer::new(Arc::new(NewmmTokenizer::from_word_list(words)));
+    let text = "กินข้าว กินปลา";
+    let splits = pre_tokenize(&pre_tokenizer, text);
+    assert_eq!(
+        splits
+            .iter()
+            .map(|(split, _)| split.as_str())
+            .collect::<Vec<_>>(),
+        ["กิน", "ข้าว", "กิน", "ปลา"]
+    );
+    for (split, (start, end)) in splits.iter() {
+        assert_eq!(&text[*start..*end], split);
+    }
+}
+
+#[test]
+fn test_pre_tokenize_keep_whitespace() {
+    let words = vec!["กิน".to_string(), "ข้าว".to_string()];
+    let pre_tokenizer = NewmmPreTokenizer::new(Arc::new(NewmmTokenizer::from_word_list(words)))
+        .keep_whitespace(true);
+    assert_eq!(
+        pre_tokenize(&pre_tokenizer, "กิน ข้าว"),
+        [
+            ("กิน".to_string(), (0, "กิน".len())),
+            (" ".to_string(), ("กิน".len(), "กิน ".len())),
+            ("ข้าว".to_string(), ("กิน ".len(), "กิน ข้าว".len())),
+        ]
+    );
+}
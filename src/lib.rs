@@ -1,5 +1,14 @@
 // SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod corpus;
+pub mod eval;
 mod four_bytes_str;
+#[cfg(feature = "hf-tokenizers")]
+pub mod hf_tokenizers;
+pub mod normalize;
+pub mod romanize;
+pub mod spell;
+#[cfg(feature = "tantivy")]
+pub mod tantivy;
 pub mod tokenizer;
@@ -0,0 +1,319 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Thai text normalization, to clean messy input before tokenization.
+ *
+ * Each rule of a `Normalizer` can be turned off:
+ *
+ * - with a `Sanitizer` (off by default), invisible characters are removed
+ *   and the text is normalized to NFC or NFKC, before the other rules
+ * - zero-width characters (U+200B, U+200C, U+200D, U+2060, U+FEFF)
+ *   are removed
+ * - nikhahit followed by sara aa (ํ + า) is composed into sara am (ำ);
+ *   tone marks between them are moved before sara am
+ * - tone marks and other upper signs typed before an above or below vowel
+ *   are moved after it, for example ก + ่ + ิ becomes ก + ิ + ่
+ * - a mark typed twice in a row is kept once
+ * - a Thai character repeated 3 times or more is kept once,
+ *   for example มากกกกก becomes มาก; Thai digits are kept
+ *
+ * The normalized text keeps the position of each character in the original
+ * text, so that spans found in the normalized text can be mapped back.
+ *
+ * ```
+ * use nlpo3::normalize::Normalizer;
+ *
+ * let normalized = Normalizer::new().normalize("มากกกกก\u{200B}ค่ะ");
+ * assert_eq!(normalized.as_str(), "มากค่ะ");
+ * assert_eq!(normalized.original_span(3, 6), (8, 11));
+ * ```
+ *
+ * A `NewmmTokenizer` with a normalizer segments the normalized text,
+ * and gives the positions of its tokens in the original text:
+ *
+ * ```
+ * use nlpo3::normalize::Normalizer;
+ * use nlpo3::tokenizer::newmm::NewmmTokenizer;
+ *
+ * let mut tokenizer = NewmmTokenizer::from_word_list(vec!["มาก".to_string()]);
+ * tokenizer.set_normalizer(Some(Normalizer::new()));
+ * let tokens = tokenizer.segment_to_tokens("มากกกกก", false, false)?;
+ * assert_eq!(tokens[0].text, "มาก");
+ * assert_eq!((tokens[0].start, tokens[0].end), (0, 7));
+ * # Ok::<(), anyhow::Error>(())
+ * ```
+*/
+pub use crate::four_bytes_str::normalized_text::NormalizedText;
+pub use crate::four_bytes_str::sanitize::{Sanitizer, UnicodeForm};
+
+use crate::four_bytes_str::normalized_text::Indexed;
+use crate::four_bytes_str::sanitize::is_zero_width;
+
+const NIKHAHIT: char = '\u{0E4D}';
+const SARA_AA: char = '\u{0E32}';
+const SARA_AM: char = '\u{0E33}';
+
+/// A Thai character repeated at least this many times is kept once.
+const MIN_REPEATS: usize = 3;
+
+/// Mai ek, mai tho, mai tri and mai chattawa.
+fn is_tone_mark(character: char) -> bool {
+    ('\u{0E48}'..='\u{0E4B}').contains(&character)
+}
+
+/// Mai han-akat, and the vowels above and below a consonant.
+fn is_vowel_mark(character: char) -> bool {
+    character == '\u{0E31}' || ('\u{0E34}'..='\u{0E3A}').contains(&character)
+}
+
+/// Vowel marks, maitaikhu, tone marks, thanthakhat, nikhahit and yamakkan.
+fn is_mark(character: char) -> bool {
+    is_vowel_mark(character) || ('\u{0E47}'..='\u{0E4E}').contains(&character)
+}
+
+/// Thai letters, vowels, marks and signs, without the Thai digits.
+fn is_repeatable(character: char) -> bool {
+    ('\u{0E01}'..='\u{0E4F}').contains(&character) || ('\u{0E5A}'..='\u{0E5B}').contains(&character)
+}
+
+/// A Thai text normalizer, with all rules turned on by default.
+///
+/// With the "serde" feature, the fields are named after the builder methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Normalizer {
+    sanitizer: Option<Sanitizer>,
+    remove_zero_width: bool,
+    compose_sara_am: bool,
+    reorder_marks: bool,
+    remove_duplicate_marks: bool,
+    reduce_repeats: bool,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Self {
+            sanitizer: None,
+            remove_zero_width: true,
+            compose_sara_am: true,
+            reorder_marks: true,
+            remove_duplicate_marks: true,
+            reduce_repeats: true,
+        }
+    }
+
+    /// Sanitize the text before the other rules, or not with `None` (the default).
+    pub fn sanitizer(mut self, sanitizer: Option<Sanitizer>) -> Self {
+        self.sanitizer = sanitizer;
+        self
+    }
+
+    /// Remove zero-width spaces, joiners and non-joiners, word joiners and BOMs.
+    pub fn remove_zero_width(mut self, enabled: bool) -> Self {
+        self.remove_zero_width = enabled;
+        self
+    }
+
+    /// Compose nikhahit and sara aa (ํ + า) into sara am (ำ).
+    pub fn compose_sara_am(mut self, enabled: bool) -> Self {
+        self.compose_sara_am = enabled;
+        self
+    }
+
+    /// Move tone marks and other upper signs after the vowel marks they precede.
+    pub fn reorder_marks(mut self, enabled: bool) -> Self {
+        self.reorder_marks = enabled;
+        self
+    }
+
+    /// Keep once a mark typed several times in a row.
+    pub fn remove_duplicate_marks(mut self, enabled: bool) -> Self {
+        self.remove_duplicate_marks = enabled;
+        self
+    }
+
+    /// Keep once a Thai character repeated 3 times or more, such as กกกกก.
+    pub fn reduce_repeats(mut self, enabled: bool) -> Self {
+        self.reduce_repeats = enabled;
+        self
+    }
+
+    /// Normalize a text, keeping the positions of its characters.
+    pub fn normalize(&self, text: &str) -> NormalizedText {
+        let mut chars: Vec<Indexed> = match &self.sanitizer {
+            Some(sanitizer) => sanitizer.sanitize_indexed(text),
+            None => text.chars().zip(0..).collect(),
+        };
+        if self.remove_zero_width {
+            chars.retain(|&(character, _)| !is_zero_width(character));
+        }
+        if self.compose_sara_am {
+            chars = compose_sara_am(&chars);
+        }
+        if self.reorder_marks || self.remove_duplicate_marks {
+            chars = self.fix_marks(&chars);
+        }
+        if self.reduce_repeats {
+            chars = reduce_repeats(&chars);
+        }
+        NormalizedText::from_indexed(&chars, text.chars().count())
+    }
+
+    /// Reorder and deduplicate each run of marks.
+    fn fix_marks(&self, chars: &[Indexed]) -> Vec<Indexed> {
+        let mut fixed: Vec<Indexed> = Vec::with_capacity(chars.len());
+        let mut run_start = 0;
+        while run_start < chars.len() {
+            let run_len = chars[run_start..]
+                .iter()
+                .take_while(|&&(character, _)| is_mark(character))
+                .count();
+            if run_len == 0 {
+                fixed.push(chars[run_start]);
+                run_start += 1;
+                continue;
+            }
+            let run = &chars[run_start..run_start + run_len];
+            let mut marks = run.to_vec();
+            if self.reorder_marks {
+                // stable, so marks of the same kind keep their order
+                marks.sort_by_key(|&(character, _)| !is_vowel_mark(character));
+                if marks != run {
+                    // reordered characters come from the whole run
+                    for mark in marks.iter_mut() {
+                        mark.1 = run[0].1;
+                    }
+                }
+            }
+            if self.remove_duplicate_marks {
+                marks.dedup_by_key(|&mut (character, _)| character);
+            }
+            fixed.extend(marks);
+            run_start += run_len;
+        }
+        fixed
+    }
+}
+
+/// Compose nikhahit, tone marks and sara aa into tone marks and sara am.
+fn compose_sara_am(chars: &[Indexed]) -> Vec<Indexed> {
+    let mut composed: Vec<Indexed> = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let (character, index) = chars[i];
+        if character == NIKHAHIT {
+            let tones_end = i
+                + 1
+                + chars[i + 1..]
+                    .iter()
+                    .take_while(|&&(character, _)| is_tone_mark(character))
+                    .count();
+            if chars
+                .get(tones_end)
+                .is_some_and(|&(next, _)| next == SARA_AA)
+            {
+                composed.extend(
+                    chars[i + 1..tones_end]
+                        .iter()
+                        .map(|&(tone, _)| (tone, index)),
+                );
+                composed.push((SARA_AM, index));
+                i = tones_end + 1;
+                continue;
+            }
+        }
+        composed.push((character, index));
+        i += 1;
+    }
+    composed
+}
+
+/// Keep once each Thai character repeated `MIN_REPEATS` times or more.
+fn reduce_repeats(chars: &[Indexed]) -> Vec<Indexed> {
+    let mut reduced: Vec<Indexed> = Vec::with_capacity(chars.len());
+    let mut run_start = 0;
+    while run_start < chars.len() {
+        let character = chars[run_start].0;
+        let run_len = chars[run_start..]
+            .iter()
+            .take_while(|&&(next, _)| next == character)
+            .count();
+        if run_len >= MIN_REPEATS && is_repeatable(character) {
+            reduced.push(chars[run_start]);
+        } else {
+            reduced.extend(&chars[run_start..run_start + run_len]);
+        }
+        run_start += run_len;
+    }
+    reduced
+}
+
+#[test]
+fn test_rules() {
+    let normalize = |text: &str| Normalizer::new().normalize(text).into_string();
+    assert_eq!(normalize("ทำ\u{200B}งาน\u{FEFF}"), "ทำงาน");
+    assert_eq!(normalize("น\u{0E4D}\u{0E49}า"), "น้ำ");
+    assert_eq!(normalize("ค\u{0E4D}า"), "คำ");
+    assert_eq!(normalize("ก\u{0E48}\u{0E34}น"), "กิ่น");
+    assert_eq!(normalize("ไก\u{0E48}\u{0E48}"), "ไก่");
+    assert_eq!(normalize("ที\u{0E48}\u{0E35}\u{0E48}"), "ที่");
+    assert_eq!(normalize("มากกกกก"), "มาก");
+    assert_eq!(normalize("ค่ะะะะ"), "ค่ะ");
+    // pairs, digits and other scripts are kept
+    assert_eq!(normalize("คล้ายกก ๑๐๐๐ wwww"), "คล้ายกก ๑๐๐๐ wwww");
+
+    let normalizer = Normalizer::new()
+        .remove_zero_width(false)
+        .compose_sara_am(false)
+        .reorder_marks(false)
+        .remove_duplicate_marks(false)
+        .reduce_repeats(false);
+    for text in [
+        "ทำ\u{200B}งาน",
+        "น\u{0E4D}\u{0E49}า",
+        "ก\u{0E48}\u{0E34}",
+        "มากกกกก",
+    ] {
+        assert_eq!(normalizer.normalize(text).as_str(), text);
+    }
+    let normalizer = Normalizer::new().sanitizer(Some(Sanitizer::new()));
+    assert_eq!(
+        normalizer
+            .normalize("\u{FEFF}คอม\u{00AD}พิวเตอร์ cafe\u{0301}")
+            .as_str(),
+        "คอมพิวเตอร์ caf\u{00E9}"
+    );
+    let normalizer = Normalizer::new().reorder_marks(false);
+    assert_eq!(
+        normalizer.normalize("ก\u{0E48}\u{0E48}\u{0E34}").as_str(),
+        "ก\u{0E48}\u{0E34}"
+    );
+}
+
+#[test]
+fn test_original_span() {
+    let normalized = Normalizer::new().normalize("\u{200B}มากกกกก\u{200B}ค่ะ");
+    assert_eq!(normalized.as_str(), "มากค่ะ");
+    assert_eq!(normalized.chars_len(), 6);
+    assert_eq!(normalized.original_span(0, 3), (0, 9));
+    assert_eq!(normalized.original_span(3, 6), (9, 12));
+    assert_eq!(normalized.original_span(6, 6), (12, 12));
+
+    // น + ํ + ้ + า: the tone mark and sara am map to the whole group
+    let normalized = Normalizer::new().normalize("น\u{0E4D}\u{0E49}าดี");
+    assert_eq!(normalized.as_str(), "น้ำดี");
+    assert_eq!(normalized.original_span(0, 3), (0, 4));
+    assert_eq!(normalized.original_span(3, 5), (4, 6));
+    assert_eq!(Normalizer::new().normalize("").original_span(0, 0), (0, 0));
+}
@@ -0,0 +1,781 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Romanization of Thai text, for search by Latin spelling and URL slugs.
+ *
+ * Text is segmented into words by a tokenizer, and each word is cut into
+ * written syllables with rules: an initial consonant or cluster, a vowel
+ * form around it, and an optional final consonant. Two engines are available:
+ *
+ * - `Engine::Rtgs`: the Royal Thai General System of Transcription,
+ *   without tones or vowel length, as on road signs (กรุงเทพ → krungthep)
+ * - `Engine::Ipa`: a phonetic transcription in the International Phonetic
+ *   Alphabet, with vowel length and tones (กรุงเทพ → kruŋ˧.tʰeːp̚˥˩)
+ *
+ * The rules read the spelling, not the pronunciation: linking vowels that
+ * are not written, as in มหาวิทยาลัย (mahawitthayalai), are missed.
+ * Such words can be given in an exceptions dictionary.
+ *
+ * ```
+ * use std::sync::Arc;
+ *
+ * use nlpo3::romanize::{Engine, Romanizer};
+ * use nlpo3::tokenizer::newmm::NewmmTokenizer;
+ *
+ * let words = ["ภาษา", "ไทย", "ข้าว", "ผัด", "จาน", "มหาวิทยาลัย"];
+ * let words = words.iter().map(|word| word.to_string()).collect();
+ * let romanizer = Romanizer::new(Arc::new(NewmmTokenizer::from_word_list(words)))
+ *     .exceptions(
+ *         Engine::Rtgs,
+ *         vec![("มหาวิทยาลัย".to_string(), "mahawitthayalai".to_string())],
+ *     );
+ * assert_eq!(romanizer.romanize("ภาษาไทย")?, "phasa thai");
+ * assert_eq!(romanizer.slug("ข้าวผัด 2 จาน")?, "khao-phat-2-chan");
+ * assert_eq!(romanizer.slug("มหาวิทยาลัย")?, "mahawitthayalai");
+ * # Ok::<(), anyhow::Error>(())
+ * ```
+*/
+use std::sync::Arc;
+
+use crate::tokenizer::newmm::NewmmTokenizer;
+use anyhow::Result as AnyResult;
+use rustc_hash::FxHashMap as HashMap;
+
+/// A romanization engine.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Engine {
+    /// Royal Thai General System of Transcription
+    #[default]
+    Rtgs,
+    /// International Phonetic Alphabet, with tones
+    Ipa,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConsonantClass {
+    Mid,
+    High,
+    Low,
+}
+
+/// A final consonant sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Final {
+    rtgs: &'static str,
+    ipa: &'static str,
+    /// Whether the syllable stays live (not a stop)
+    sonorant: bool,
+}
+
+const FINAL_K: Final = Final {
+    rtgs: "k",
+    ipa: "k̚",
+    sonorant: false,
+};
+const FINAL_T: Final = Final {
+    rtgs: "t",
+    ipa: "t̚",
+    sonorant: false,
+};
+const FINAL_P: Final = Final {
+    rtgs: "p",
+    ipa: "p̚",
+    sonorant: false,
+};
+const FINAL_NG: Final = Final {
+    rtgs: "ng",
+    ipa: "ŋ",
+    sonorant: true,
+};
+const FINAL_N: Final = Final {
+    rtgs: "n",
+    ipa: "n",
+    sonorant: true,
+};
+const FINAL_M: Final = Final {
+    rtgs: "m",
+    ipa: "m",
+    sonorant: true,
+};
+const FINAL_Y: Final = Final {
+    rtgs: "i",
+    ipa: "j",
+    sonorant: true,
+};
+const FINAL_W: Final = Final {
+    rtgs: "o",
+    ipa: "w",
+    sonorant: true,
+};
+
+/// Sounds of a consonant: as an initial (RTGS, IPA), as a final, and its class.
+type ConsonantSounds = (&'static str, &'static str, Option<Final>, ConsonantClass);
+
+fn consonant(character: char) -> Option<ConsonantSounds> {
+    use ConsonantClass::{High, Low, Mid};
+
+    let sounds = match character {
+        'ก' => ("k", "k", Some(FINAL_K), Mid),
+        'ข' | 'ฃ' => ("kh", "kʰ", Some(FINAL_K), High),
+        'ค' | 'ฅ' | 'ฆ' => ("kh", "kʰ", Some(FINAL_K), Low),
+        'ง' => ("ng", "ŋ", Some(FINAL_NG), Low),
+        'จ' => ("ch", "tɕ", Some(FINAL_T), Mid),
+        'ฉ' => ("ch", "tɕʰ", None, High),
+        'ช' | 'ฌ' => ("ch", "tɕʰ", Some(FINAL_T), Low),
+        'ซ' => ("s", "s", Some(FINAL_T), Low),
+        'ญ' => ("y", "j", Some(FINAL_N), Low),
+        'ฎ' | 'ด' => ("d", "d", Some(FINAL_T), Mid),
+        'ฏ' | 'ต' => ("t", "t", Some(FINAL_T), Mid),
+        'ฐ' | 'ถ' => ("th", "tʰ", Some(FINAL_T), High),
+        'ฑ' | 'ฒ' | 'ท' | 'ธ' => ("th", "tʰ", Some(FINAL_T), Low),
+        'ณ' | 'น' => ("n", "n", Some(FINAL_N), Low),
+        'บ' => ("b", "b", Some(FINAL_P), Mid),
+        'ป' => ("p", "p", Some(FINAL_P), Mid),
+        'ผ' => ("ph", "pʰ", None, High),
+        'ฝ' => ("f", "f", None, High),
+        'พ' | 'ภ' => ("ph", "pʰ", Some(FINAL_P), Low),
+        'ฟ' => ("f", "f", Some(FINAL_P), Low),
+        'ม' => ("m", "m", Some(FINAL_M), Low),
+        'ย' => ("y", "j", Some(FINAL_Y), Low),
+        'ร' => ("r", "r", Some(FINAL_N), Low),
+        'ล' | 'ฬ' => ("l", "l", Some(FINAL_N), Low),
+        'ว' => ("w", "w", Some(FINAL_W), Low),
+        'ศ' | 'ษ' | 'ส' => ("s", "s", Some(FINAL_T), High),
+        'ห' => ("h", "h", None, High),
+        'อ' => ("", "ʔ", None, Mid),
+        'ฮ' => ("h", "h", None, Low),
+        _ => return None,
+    };
+    Some(sounds)
+}
+
+fn is_consonant(character: char) -> bool {
+    consonant(character).is_some()
+}
+
+/// Vowels and signs that follow a consonant, and cannot start a syllable.
+fn is_following_vowel(character: char) -> bool {
+    matches!(
+        character,
+        'ะ' | 'ั' | 'า' | 'ำ' | 'ิ' | 'ี' | 'ึ' | 'ื' | 'ุ' | 'ู' | '็' | 'ๅ'
+    )
+}
+
+/// Mai ek, mai tho, mai tri and mai chattawa.
+fn is_tone_mark(character: char) -> bool {
+    ('\u{0E48}'..='\u{0E4B}').contains(&character)
+}
+
+/// Length of the initial consonant or cluster of two consonants
+/// that could start at `chars[0]`, longest first.
+fn initial_lengths(chars: &[char]) -> Vec<usize> {
+    match chars {
+        [first, second, ..] if is_cluster(*first, *second) => vec![2, 1],
+        [first, ..] if is_consonant(*first) => vec![1],
+        _ => vec![],
+    }
+}
+
+/// A consonant cluster, or a consonant led by ห or อ.
+fn is_cluster(first: char, second: char) -> bool {
+    match second {
+        'ร' => "กขคตปพบดฟทจซศสห".contains(first),
+        'ล' => "กขคปพผบฟห".contains(first),
+        'ว' => "กขคห".contains(first),
+        'ง' | 'ญ' | 'น' | 'ม' => first == 'ห',
+        'ย' => first == 'ห' || first == 'อ',
+        _ => false,
+    }
+}
+
+/// Sounds of an initial consonant or cluster: RTGS, IPA, and tone class.
+fn initial_sounds(initial: &[char]) -> (String, String, ConsonantClass) {
+    let (rtgs, ipa, _, class) = consonant(initial[0]).unwrap();
+    let second = match initial.get(1) {
+        Some(second) => *second,
+        None => return (rtgs.to_string(), ipa.to_string(), class),
+    };
+    let (second_rtgs, second_ipa, _, _) = consonant(second).unwrap();
+    match (initial[0], second) {
+        // ห and อ are silent, and set the tone class
+        ('ห', _) => (
+            second_rtgs.to_string(),
+            second_ipa.to_string(),
+            ConsonantClass::High,
+        ),
+        ('อ', _) => (
+            second_rtgs.to_string(),
+            second_ipa.to_string(),
+            ConsonantClass::Mid,
+        ),
+        // ทร sounds like ซ
+        ('ท', 'ร') => ("s".to_string(), "s".to_string(), class),
+        // ร is silent after จ, ซ, ศ and ส
+        ('จ', 'ร') | ('ซ', 'ร') | ('ศ', 'ร') | ('ส', 'ร') => {
+            (rtgs.to_string(), ipa.to_string(), class)
+        }
+        _ => (
+            format!("{}{}", rtgs, second_rtgs),
+            format!("{}{}", ipa, second_ipa),
+            class,
+        ),
+    }
+}
+
+/// A written vowel form, where `C` is the initial and `F` the final consonant.
+struct VowelForm {
+    pattern: &'static str,
+    rtgs: &'static str,
+    ipa: &'static str,
+    long: bool,
+    /// Whether an open syllable ends with a sonorant, as ไ- (ai)
+    sonorant: bool,
+}
+
+const fn vowel(
+    pattern: &'static str,
+    rtgs: &'static str,
+    ipa: &'static str,
+    long: bool,
+    sonorant: bool,
+) -> VowelForm {
+    VowelForm {
+        pattern,
+        rtgs,
+        ipa,
+        long,
+        sonorant,
+    }
+}
+
+/// Vowel forms, tried in order: longer forms before their prefixes.
+/// Final ย and ว are glides, so -าย is -า with the final ย (ai).
+const VOWEL_FORMS: &[VowelForm] = &[
+    vowel("เCือะ", "uea", "ɯa", false, false),
+    vowel("เCือF", "uea", "ɯːa", true, false),
+    vowel("เCือ", "uea", "ɯːa", true, false),
+    vowel("เCียะ", "ia", "ia", false, false),
+    vowel("เCียF", "ia", "iːa", true, false),
+    vowel("เCีย", "ia", "iːa", true, false),
+    vowel("เCาะ", "o", "ɔ", false, false),
+    vowel("เCา", "ao", "aw", false, true),
+    vowel("เCอะ", "oe", "ɤ", false, false),
+    vowel("เCอF", "oe", "ɤː", true, false),
+    vowel("เCอ", "oe", "ɤː", true, false),
+    vowel("เCิF", "oe", "ɤː", true, false),
+    vowel("เC็F", "e", "e", false, false),
+    vowel("เCะ", "e", "e", false, false),
+    vowel("เCย", "oei", "ɤːj", true, true),
+    vowel("เCF", "e", "eː", true, false),
+    vowel("เC", "e", "eː", true, false),
+    vowel("แC็F", "ae", "ɛ", false, false),
+    vowel("แCะ", "ae", "ɛ", false, false),
+    vowel("แCF", "ae", "ɛː", true, false),
+    vowel("แC", "ae", "ɛː", true, false),
+    vowel("โCะ", "o", "o", false, false),
+    vowel("โCF", "o", "oː", true, false),
+    vowel("โC", "o", "oː", true, false),
+    vowel("ไCย", "ai", "aj", false, true),
+    vowel("ไC", "ai", "aj", false, true),
+    vowel("ใC", "ai", "aj", false, true),
+    vowel("Cัวะ", "ua", "ua", false, false),
+    vowel("Cัว", "ua", "uːa", true, false),
+    vowel("CวF", "ua", "uːa", true, false),
+    vowel("CรรF", "a", "a", false, false),
+    vowel("Cรร", "an", "an", false, true),
+    vowel("Cะ", "a", "a", false, false),
+    vowel("CัF", "a", "a", false, false),
+    vowel("CาF", "a", "aː", true, false),
+    vowel("Cา", "a", "aː", true, false),
+    vowel("Cำ", "am", "am", false, true),
+    vowel("CิF", "i", "i", false, false),
+    vowel("Cิ", "i", "i", false, false),
+    vowel("CีF", "i", "iː", true, false),
+    vowel("Cี", "i", "iː", true, false),
+    vowel("CึF", "ue", "ɯ", false, false),
+    vowel("Cึ", "ue", "ɯ", false, false),
+    vowel("CือF", "ue", "ɯː", true, false),
+    vowel("Cือ", "ue", "ɯː", true, false),
+    vowel("CืF", "ue", "ɯː", true, false),
+    vowel("Cื", "ue", "ɯː", true, false),
+    vowel("CุF", "u", "u", false, false),
+    vowel("Cุ", "u", "u", false, false),
+    vowel("CูF", "u", "uː", true, false),
+    vowel("Cู", "u", "uː", true, false),
+    vowel("CอF", "o", "ɔː", true, false),
+    vowel("Cอ", "o", "ɔː", true, false),
+    vowel("C็", "o", "ɔ", false, false),
+    // no written vowel: o between two consonants, a after one
+    vowel("CF", "o", "o", false, false),
+    vowel("C", "a", "a", false, false),
+];
+
+/// A written syllable.
+struct Syllable<'a> {
+    initial: &'a [char],
+    vowel: &'static VowelForm,
+    final_sound: Option<Final>,
+    tone_mark: Option<char>,
+}
+
+/// Match the pattern of a vowel form from `position` of `chars`,
+/// and return where the syllable ends.
+/// `C` tries a cluster before a single consonant.
+/// `tone_marks` has the tone mark written after each character, if any.
+fn match_form<'a>(
+    pattern: &[char],
+    chars: &'a [char],
+    tone_marks: &[Option<char>],
+    position: usize,
+    syllable: &mut Syllable<'a>,
+) -> Option<usize> {
+    let (&expected, pattern_rest) = match pattern.split_first() {
+        Some(split) => split,
+        // a syllable cannot end before a following vowel
+        None => {
+            return match chars.get(position) {
+                Some(&next) if is_following_vowel(next) => None,
+                _ => Some(position),
+            }
+        }
+    };
+    match expected {
+        'C' => initial_lengths(&chars[position..])
+            .into_iter()
+            .find_map(|length| {
+                syllable.initial = &chars[position..position + length];
+                match_form(pattern_rest, chars, tone_marks, position + length, syllable)
+            }),
+        'F' => {
+            // a tone mark is written on an initial, never on a final
+            if tone_marks.get(position).is_some_and(|mark| mark.is_some()) {
+                return None;
+            }
+            let final_sound = chars.get(position).and_then(|&c| consonant(c)?.2)?;
+            syllable.final_sound = Some(final_sound);
+            let mut next = position + 1;
+            // ร after a final stop is silent, as in จักร and สมุทร
+            if !final_sound.sonorant
+                && chars.get(next) == Some(&'ร')
+                && chars.get(next + 1).is_none_or(|&c| is_consonant(c))
+            {
+                next += 1;
+            }
+            match_form(pattern_rest, chars, tone_marks, next, syllable)
+        }
+        _ if chars.get(position) == Some(&expected) => {
+            match_form(pattern_rest, chars, tone_marks, position + 1, syllable)
+        }
+        _ => None,
+    }
+}
+
+/// Remove silent letters, marked with thanthakhat: จันทร์ is read จัน.
+fn remove_silent_letters(chars: &[char]) -> Vec<char> {
+    let mut kept: Vec<char> = Vec::with_capacity(chars.len());
+    for &character in chars {
+        if character != '์' {
+            kept.push(character);
+            continue;
+        }
+        // the silent consonant, with its vowel as in พันธุ์
+        if kept.last().is_some_and(|&c| c == 'ิ' || c == 'ุ') {
+            kept.pop();
+        }
+        let silent = kept.pop();
+        // ตร์ and ทร์ are both silent, as in ศาสตร์
+        let len = kept.len();
+        if silent == Some('ร')
+            && len >= 2
+            && "ตทด".contains(kept[len - 1])
+            && is_consonant(kept[len - 2])
+            && kept[len - 2] != 'อ'
+        {
+            kept.pop();
+        }
+    }
+    kept
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tone {
+    Mid,
+    Low,
+    Falling,
+    High,
+    Rising,
+}
+
+impl Tone {
+    fn ipa(self) -> &'static str {
+        match self {
+            Tone::Mid => "˧",
+            Tone::Low => "˨˩",
+            Tone::Falling => "˥˩",
+            Tone::High => "˦˥",
+            Tone::Rising => "˩˩˦",
+        }
+    }
+}
+
+impl Syllable<'_> {
+    /// A live syllable ends with a long vowel or a sonorant, a dead one with
+    /// a short vowel or a stop.
+    fn is_live(&self) -> bool {
+        match self.final_sound {
+            Some(final_sound) => final_sound.sonorant,
+            None => self.vowel.long || self.vowel.sonorant,
+        }
+    }
+
+    fn tone(&self, class: ConsonantClass) -> Tone {
+        use ConsonantClass::{High, Low, Mid};
+
+        match (self.tone_mark, class) {
+            (Some('่'), Low) => Tone::Falling,
+            (Some('่'), _) => Tone::Low,
+            (Some('้'), Low) => Tone::High,
+            (Some('้'), _) => Tone::Falling,
+            (Some('๊'), _) => Tone::High,
+            (Some('๋'), _) => Tone::Rising,
+            (_, Mid) if self.is_live() => Tone::Mid,
+            (_, Low) if self.is_live() => Tone::Mid,
+            (_, High) if self.is_live() => Tone::Rising,
+            (_, Low) if self.vowel.long => Tone::Falling,
+            (_, Low) => Tone::High,
+            (_, _) => Tone::Low,
+        }
+    }
+
+    fn romanize(&self, engine: Engine) -> String {
+        let (initial_rtgs, initial_ipa, class) = initial_sounds(self.initial);
+        match engine {
+            Engine::Rtgs => format!(
+                "{}{}{}",
+                initial_rtgs,
+                self.vowel.rtgs,
+                self.final_sound.map_or("", |final_sound| final_sound.rtgs)
+            ),
+            Engine::Ipa => {
+                let coda = match self.final_sound {
+                    Some(final_sound) => final_sound.ipa,
+                    // a short open vowel ends with a glottal stop
+                    None if !self.vowel.long && !self.vowel.sonorant => "ʔ",
+                    None => "",
+                };
+                format!(
+                    "{}{}{}{}",
+                    initial_ipa,
+                    self.vowel.ipa,
+                    coda,
+                    self.tone(class).ipa()
+                )
+            }
+        }
+    }
+}
+
+/// Romanize a Thai word with rules, syllable by syllable.
+/// RTGS syllables are joined, IPA syllables are separated by a dot.
+fn romanize_syllables(word: &str, engine: Engine) -> String {
+    let chars: Vec<char> = word
+        .replace("ฤๅ", "รือ")
+        .replace('ฤ', "รึ")
+        .replace("ฦๅ", "ลือ")
+        .replace('ฦ', "ลึ")
+        .chars()
+        .filter(|&character| character != 'ๆ' && character != 'ฯ')
+        .collect();
+    let chars = remove_silent_letters(&chars);
+    // tone marks are matched apart, each with the character before it
+    let mut tone_marks: Vec<Option<char>> = vec![];
+    let mut letters: Vec<char> = Vec::with_capacity(chars.len());
+    for character in chars {
+        if is_tone_mark(character) {
+            if let Some(tone_mark) = tone_marks.last_mut() {
+                *tone_mark = Some(character);
+            }
+        } else {
+            letters.push(character);
+            tone_marks.push(None);
+        }
+    }
+
+    let mut syllables: Vec<String> = vec![];
+    let mut position = 0;
+    while position < letters.len() {
+        let found = VOWEL_FORMS.iter().find_map(|form| {
+            let pattern: Vec<char> = form.pattern.chars().collect();
+            let mut syllable = Syllable {
+                initial: &[],
+                vowel: form,
+                final_sound: None,
+                tone_mark: None,
+            };
+            let end = match_form(&pattern, &letters, &tone_marks, position, &mut syllable)?;
+            if !pattern.contains(&'F') {
+                syllable.final_sound = None;
+            }
+            syllable.tone_mark = tone_marks[position..end].iter().find_map(|&mark| mark);
+            Some((syllable, end))
+        });
+        match found {
+            Some((syllable, end)) => {
+                syllables.push(syllable.romanize(engine));
+                position = end;
+            }
+            None => {
+                // a Thai digit, or a letter out of any syllable
+                let character = letters[position];
+                if let Some(digit) = ascii_digit(character) {
+                    syllables.push(digit.to_string());
+                } else if !('\u{0E00}'..='\u{0E7F}').contains(&character) {
+                    syllables.push(character.to_string());
+                }
+                position += 1;
+            }
+        }
+    }
+    match engine {
+        Engine::Rtgs => syllables.concat(),
+        Engine::Ipa => syllables.join("."),
+    }
+}
+
+/// Whether a token is Thai text to romanize.
+fn is_thai(text: &str) -> bool {
+    text.chars()
+        .any(|character| ('\u{0E01}'..='\u{0E4F}').contains(&character))
+}
+
+/// A Thai digit as an ASCII digit.
+fn ascii_digit(character: char) -> Option<char> {
+    match character {
+        '๐'..='๙' => char::from_digit(character as u32 - '๐' as u32, 10),
+        _ => None,
+    }
+}
+
+/// Thai digits as ASCII digits, other characters as they are.
+fn to_ascii_digits(text: &str) -> String {
+    text.chars()
+        .map(|character| ascii_digit(character).unwrap_or(character))
+        .collect()
+}
+
+/// A romanizer of Thai text, backed by a [shared](NewmmTokenizer#sharing) `NewmmTokenizer`.
+#[derive(Clone, Debug)]
+pub struct Romanizer {
+    tokenizer: Arc<NewmmTokenizer>,
+    engine: Engine,
+    /// Exceptions of each engine
+    exceptions: HashMap<Engine, HashMap<String, String>>,
+    safe: bool,
+}
+
+impl Romanizer {
+    /// Create a romanizer with the RTGS engine and no exceptions.
+    pub fn new(tokenizer: Arc<NewmmTokenizer>) -> Self {
+        Self {
+            tokenizer,
+            engine: Engine::Rtgs,
+            exceptions: HashMap::default(),
+            safe: true,
+        }
+    }
+
+    /// Set the romanization engine (default: `Engine::Rtgs`).
+    pub fn engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Add words with their romanization by `engine`,
+    /// used instead of the rules when romanizing with that engine.
+    /// An exception replaces a previous one.
+    ///
+    /// A word is looked up as segmented: add words that the dictionary
+    /// of the tokenizer does not have to it too.
+    pub fn exceptions<I: IntoIterator<Item = (String, String)>>(
+        mut self,
+        engine: Engine,
+        exceptions: I,
+    ) -> Self {
+        self.exceptions
+            .entry(engine)
+            .or_default()
+            .extend(exceptions);
+        self
+    }
+
+    /// Set [safe mode](NewmmTokenizer#safe-mode) of the segmentation (default: true).
+    pub fn safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Romanize one word, without segmenting it.
+    pub fn romanize_word(&self, word: &str) -> String {
+        self.romanize_word_with(word, self.engine)
+    }
+
+    fn romanize_word_with(&self, word: &str, engine: Engine) -> String {
+        match self
+            .exceptions
+            .get(&engine)
+            .and_then(|exceptions| exceptions.get(word))
+        {
+            Some(romanized) => romanized.clone(),
+            None => romanize_syllables(word, engine),
+        }
+    }
+
+    /// Romanize a text, with Thai words separated by a space.
+    /// Other text is kept, with Thai digits as ASCII digits.
+    /// ๆ repeats the word before it.
+    pub fn romanize(&self, text: &str) -> AnyResult<String> {
+        self.romanize_with(text, self.engine)
+    }
+
+    fn romanize_with(&self, text: &str, engine: Engine) -> AnyResult<String> {
+        let tokens = self.tokenizer.segment_to_tokens(text, self.safe, false)?;
+        let mut romanized = String::with_capacity(text.len());
+        let mut last_word: Option<String> = None;
+        for token in tokens {
+            if !is_thai(&token.text) {
+                romanized.push_str(&to_ascii_digits(&token.text));
+                last_word = None;
+                continue;
+            }
+            let word = match (token.text.trim(), &last_word) {
+                ("ๆ", Some(last_word)) => last_word.clone(),
+                (word, _) => self.romanize_word_with(word, engine),
+            };
+            if word.is_empty() {
+                continue;
+            }
+            if last_word.is_some() {
+                romanized.push(' ');
+            }
+            romanized.push_str(&word);
+            last_word = Some(word);
+        }
+        Ok(romanized)
+    }
+
+    /// A URL slug of a text: its RTGS romanization in lowercase ASCII
+    /// letters and digits, with words separated by `-`.
+    /// Exceptions given for the RTGS engine are used, whatever the current engine.
+    pub fn slug(&self, text: &str) -> AnyResult<String> {
+        let romanized = self.romanize_with(text, Engine::Rtgs)?;
+        let mut slug = String::with_capacity(romanized.len());
+        for character in romanized.chars() {
+            if character.is_ascii_alphanumeric() {
+                slug.push(character.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        if slug.ends_with('-') {
+            slug.pop();
+        }
+        Ok(slug)
+    }
+}
+
+#[test]
+fn test_rtgs() {
+    let romanize = |word: &str| romanize_syllables(word, Engine::Rtgs);
+    assert_eq!(romanize("กรุงเทพ"), "krungthep");
+    assert_eq!(romanize("สวัสดี"), "sawatdi");
+    assert_eq!(romanize("ภาษา"), "phasa");
+    assert_eq!(romanize("ไทย"), "thai");
+    assert_eq!(romanize("ข้าว"), "khao");
+    assert_eq!(romanize("เมือง"), "mueang");
+    assert_eq!(romanize("น้ำ"), "nam");
+    assert_eq!(romanize("ความ"), "khwam");
+    assert_eq!(romanize("สวน"), "suan");
+    assert_eq!(romanize("หมา"), "ma");
+    assert_eq!(romanize("อยู่"), "yu");
+    assert_eq!(romanize("เงิน"), "ngoen");
+    assert_eq!(romanize("เด็ก"), "dek");
+    assert_eq!(romanize("ตลาด"), "talat");
+    assert_eq!(romanize("ประเทศ"), "prathet");
+    assert_eq!(romanize("ทราย"), "sai");
+    assert_eq!(romanize("จริง"), "ching");
+    assert_eq!(romanize("กรรม"), "kam");
+    assert_eq!(romanize("จันทร์"), "chan");
+    assert_eq!(romanize("ฟิล์ม"), "fim");
+    assert_eq!(romanize("ฤดู"), "ruedu");
+    assert_eq!(romanize("แมว"), "maeo");
+    assert_eq!(romanize("เลย"), "loei");
+    assert_eq!(romanize("ก็"), "ko");
+    assert_eq!(romanize("อร่อย"), "aroi");
+    assert_eq!(romanize("สมุทร"), "samut");
+    assert_eq!(romanize("เพชร"), "phet");
+    assert_eq!(romanize("๒๕๖๙"), "2569");
+}
+
+#[test]
+fn test_ipa() {
+    let transcribe = |word: &str| romanize_syllables(word, Engine::Ipa);
+    assert_eq!(transcribe("กรุงเทพ"), "kruŋ˧.tʰeːp̚˥˩");
+    assert_eq!(transcribe("ข้าว"), "kʰaːw˥˩");
+    assert_eq!(transcribe("ไม่"), "maj˥˩");
+    assert_eq!(transcribe("ขา"), "kʰaː˩˩˦");
+    assert_eq!(transcribe("จะ"), "tɕaʔ˨˩");
+    assert_eq!(transcribe("รัก"), "rak̚˦˥");
+    assert_eq!(transcribe("มาก"), "maːk̚˥˩");
+    assert_eq!(transcribe("หมา"), "maː˩˩˦");
+    assert_eq!(transcribe("อยู่"), "juː˨˩");
+    assert_eq!(transcribe("โต๊ะ"), "toʔ˦˥");
+    assert_eq!(transcribe("เมือง"), "mɯːaŋ˧");
+}
+
+#[test]
+fn test_romanizer() {
+    let dict = "ภาษา\nไทย\nข้าว\nผัด\nจาน\nเด็ก\nมหาวิทยาลัย\n";
+    let romanizer = Romanizer::new(Arc::new(
+        NewmmTokenizer::from_bytes(dict.as_bytes()).unwrap(),
+    ));
+    assert_eq!(romanizer.romanize("ภาษาไทย").unwrap(), "phasa thai");
+    assert_eq!(
+        romanizer.romanize("ข้าวผัด ๒ จาน").unwrap(),
+        "khao phat 2 chan"
+    );
+    assert_eq!(romanizer.romanize("เด็กๆ").unwrap(), "dek dek");
+    assert_eq!(romanizer.slug("ข้าวผัด 2 จาน!").unwrap(), "khao-phat-2-chan");
+    assert_eq!(romanizer.slug("Thai ภาษาไทย").unwrap(), "thai-phasa-thai");
+
+    let romanizer = romanizer.exceptions(
+        Engine::Rtgs,
+        vec![("มหาวิทยาลัย".to_string(), "mahawitthayalai".to_string())],
+    );
+    assert_eq!(romanizer.romanize_word("มหาวิทยาลัย"), "mahawitthayalai");
+    assert_eq!(
+        romanizer.slug("มหาวิทยาลัยไทย").unwrap(),
+        "mahawitthayalai-thai"
+    );
+
+    // RTGS exceptions are not used by another engine, but still by slug
+    let romanizer = romanizer.engine(Engine::Ipa);
+    assert_eq!(romanizer.romanize("ภาษาไทย").unwrap(), "pʰaː˧.saː˩˩˦ tʰaj˧");
+    assert_eq!(
+        romanizer.romanize("มหาวิทยาลัย").unwrap(),
+        romanize_syllables("มหาวิทยาลัย", Engine::Ipa)
+    );
+    assert_eq!(romanizer.slug("มหาวิทยาลัย").unwrap(), "mahawitthayalai");
+
+    // IPA exceptions are used by the IPA engine only,
+    // whether they are given before or after setting it
+    let ipa_exceptions = vec![("ไทย".to_string(), "tʰaj".to_string())];
+    let romanizer = romanizer.exceptions(Engine::Ipa, ipa_exceptions.clone());
+    assert_eq!(romanizer.romanize("ภาษาไทย").unwrap(), "pʰaː˧.saː˩˩˦ tʰaj");
+    assert_eq!(romanizer.slug("ภาษาไทย").unwrap(), "phasa-thai");
+    let romanizer = romanizer
+        .engine(Engine::Rtgs)
+        .exceptions(Engine::Ipa, ipa_exceptions)
+        .engine(Engine::Ipa);
+    assert_eq!(romanizer.romanize("ภาษาไทย").unwrap(), "pʰaː˧.saː˩˩˦ tʰaj");
+}
@@ -0,0 +1,252 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Spelling checker and corrector, with the dictionary of a tokenizer.
+ *
+ * A misspelling is a run of Thai text that the tokenizer cannot match
+ * with dictionary words (tokens of kind `TokenKind::Unknown`).
+ * Digits, Latin text and Thai signs such as ๆ and ฯ are not checked.
+ *
+ * Corrections are ranked like in Peter Norvig's spelling corrector:
+ * the closest dictionary words first, then the most frequent ones.
+ * The edit distance is Thai-aware, see `tokenizer::fuzzy::EditCosts`,
+ * so a wrong tone mark or a consonant with the same sound is a closer
+ * correction than other edits. Word frequencies come from the dictionary,
+ * see `tokenizer::WordInfo`.
+ *
+ * ```
+ * use std::sync::Arc;
+ *
+ * use nlpo3::spell::SpellChecker;
+ * use nlpo3::tokenizer::newmm::NewmmTokenizer;
+ *
+ * let words = vec!["ไป".to_string(), "เที่ยว".to_string(), "ศาลา".to_string()];
+ * let checker = SpellChecker::new(Arc::new(NewmmTokenizer::from_word_list(words)));
+ * let misspellings = checker.check("ไปเที่ยวสาลา")?;
+ * assert_eq!(misspellings[0].text, "สาลา");
+ * assert_eq!(misspellings[0].suggestions[0].word, "ศาลา");
+ * assert_eq!(checker.correct("ไปเที่ยวสาลา")?, "ไปเที่ยวศาลา");
+ * # Ok::<(), anyhow::Error>(())
+ * ```
+*/
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use crate::tokenizer::fuzzy::EditCosts;
+use crate::tokenizer::newmm::NewmmTokenizer;
+use crate::tokenizer::token::TokenKind;
+use anyhow::Result as AnyResult;
+
+/// A correction of a misspelled word.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// Weighted edit distance from the misspelled word
+    pub distance: f32,
+    /// Frequency of the word in the dictionary, if any
+    pub frequency: Option<u64>,
+}
+
+/// A misspelled word and its position in the checked text.
+///
+/// `start` and `end` are character (Unicode scalar value) indices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Misspelling {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    /// Corrections, best first; empty if no dictionary word is close enough
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// A spelling checker backed by a [shared](NewmmTokenizer#sharing) `NewmmTokenizer`.
+#[derive(Clone, Debug)]
+pub struct SpellChecker {
+    tokenizer: Arc<NewmmTokenizer>,
+    costs: EditCosts,
+    max_distance: f32,
+    max_suggestions: usize,
+    safe: bool,
+}
+
+/// Whether a token is Thai text to check, with at least one Thai consonant.
+fn is_checked(kind: TokenKind, text: &str) -> bool {
+    kind == TokenKind::Unknown
+        && text
+            .chars()
+            .any(|character| ('\u{0E01}'..='\u{0E2E}').contains(&character))
+}
+
+impl SpellChecker {
+    /// Create a checker with the default edit costs,
+    /// suggesting at most 5 words within a distance of 1.
+    pub fn new(tokenizer: Arc<NewmmTokenizer>) -> Self {
+        Self {
+            tokenizer,
+            costs: EditCosts::default(),
+            max_distance: 1.0,
+            max_suggestions: 5,
+            safe: true,
+        }
+    }
+
+    /// Set the costs of the edits (default: `EditCosts::default()`).
+    pub fn costs(mut self, costs: EditCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Set the maximum edit distance of a suggestion (default: 1).
+    pub fn max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Set the maximum number of suggestions for a word (default: 5).
+    pub fn max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Set [safe mode](NewmmTokenizer#safe-mode) of the segmentation (default: true).
+    pub fn safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Whether a word is in the dictionary.
+    pub fn is_known(&self, word: &str) -> bool {
+        self.tokenizer.contains_word(word)
+    }
+
+    /// Corrections of a word, best first.
+    /// A dictionary word is its own best correction, at distance 0.
+    pub fn suggest(&self, word: &str) -> Vec<Suggestion> {
+        let mut suggestions: Vec<Suggestion> = self
+            .tokenizer
+            .fuzzy_lookup(word, self.max_distance, &self.costs)
+            .into_iter()
+            .map(|found| Suggestion {
+                frequency: self
+                    .tokenizer
+                    .word_info(&found.word)
+                    .and_then(|info| info.frequency),
+                word: found.word,
+                distance: found.distance,
+            })
+            .collect();
+        // the lookup sorts by distance, then by word
+        suggestions.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then_with(|| Reverse(a.frequency).cmp(&Reverse(b.frequency)))
+        });
+        suggestions.truncate(self.max_suggestions);
+        suggestions
+    }
+
+    /// Find the misspelled words of a text, with their corrections.
+    /// Adjacent unknown tokens are reported as one misspelling.
+    pub fn check(&self, text: &str) -> AnyResult<Vec<Misspelling>> {
+        let tokens = self.tokenizer.segment_to_tokens(text, self.safe, false)?;
+        let mut misspellings: Vec<Misspelling> = vec![];
+        for token in tokens {
+            if !is_checked(token.kind, &token.text) {
+                continue;
+            }
+            match misspellings.last_mut() {
+                Some(last) if last.end == token.start => {
+                    last.text.push_str(&token.text);
+                    last.end = token.end;
+                }
+                _ => misspellings.push(Misspelling {
+                    text: token.text,
+                    start: token.start,
+                    end: token.end,
+                    suggestions: vec![],
+                }),
+            }
+        }
+        for misspelling in misspellings.iter_mut() {
+            misspelling.suggestions = self.suggest(&misspelling.text);
+        }
+        Ok(misspellings)
+    }
+
+    /// Replace each misspelled word of a text with its best correction.
+    /// Words without a correction are kept.
+    pub fn correct(&self, text: &str) -> AnyResult<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut corrected = String::with_capacity(text.len());
+        let mut copied_until = 0;
+        for misspelling in self.check(text)? {
+            if let Some(best) = misspelling.suggestions.first() {
+                corrected.extend(&chars[copied_until..misspelling.start]);
+                corrected.push_str(&best.word);
+                copied_until = misspelling.end;
+            }
+        }
+        corrected.extend(&chars[copied_until..]);
+        Ok(corrected)
+    }
+}
+
+#[cfg(test)]
+fn test_checker() -> SpellChecker {
+    let dict = "กิน\nข้าว\nที่\nไป\nเที่ยว\nศาลา\t100\nศาล\t50\nสาลี\t80\nไก่\n";
+    SpellChecker::new(Arc::new(
+        NewmmTokenizer::from_bytes(dict.as_bytes()).unwrap(),
+    ))
+}
+
+#[test]
+fn test_suggest() {
+    let checker = test_checker();
+    let words = |word: &str| -> Vec<String> {
+        checker
+            .suggest(word)
+            .into_iter()
+            .map(|suggestion| suggestion.word)
+            .collect()
+    };
+    // a consonant of the same sound is closer than another vowel
+    assert_eq!(words("สาลา"), ["ศาลา", "สาลี"]);
+    assert_eq!(words("ไก้"), ["ไก่"]);
+    assert_eq!(words("ศาลา"), ["ศาลา", "ศาล"]);
+    assert!(words("ปลาทู").is_empty());
+
+    // closest first, whatever the frequency
+    let checker = checker.max_distance(2.0).max_suggestions(2);
+    let suggestions = checker.suggest("สาลู");
+    assert_eq!(suggestions[0].word, "สาลี");
+    assert!(suggestions[0].distance < suggestions[1].distance);
+    assert_eq!(suggestions[1].word, "ศาลา");
+    assert_eq!(suggestions[1].frequency, Some(100));
+
+    // same distance, most frequent first, instead of the word order
+    let checker = SpellChecker::new(Arc::new(
+        NewmmTokenizer::from_bytes("กาง\t10\nกาว\t90\n".as_bytes()).unwrap(),
+    ));
+    let suggestions = checker.suggest("กาน");
+    assert_eq!(suggestions.len(), 2);
+    assert_eq!(suggestions[0].distance, suggestions[1].distance);
+    assert_eq!(suggestions[0].word, "กาว");
+    assert_eq!(suggestions[1].word, "กาง");
+}
+
+#[test]
+fn test_check_and_correct() {
+    let checker = test_checker();
+    let misspellings = checker.check("ไปเที่ยวสาลา 123 กินไก้").unwrap();
+    assert_eq!(misspellings.len(), 2);
+    assert_eq!(misspellings[0].text, "สาลา");
+    assert_eq!((misspellings[0].start, misspellings[0].end), (8, 12));
+    assert_eq!(misspellings[0].suggestions[0].word, "ศาลา");
+    assert_eq!(misspellings[1].text, "ไก้");
+    assert_eq!(
+        checker.correct("ไปเที่ยวสาลา 123 กินไก้").unwrap(),
+        "ไปเที่ยวศาลา 123 กินไก่"
+    );
+    assert!(checker.check("กินข้าวที่ศาลา").unwrap().is_empty());
+}
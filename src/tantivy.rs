@@ -0,0 +1,186 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Tokenizer for the tantivy search engine library.
+ *
+ * Register it for a text field, so Thai text can be indexed directly,
+ * with `index.tokenizers().register("newmm", tokenizer)`:
+ *
+ * ```
+ * use std::sync::Arc;
+ *
+ * use nlpo3::tantivy::NewmmTantivyTokenizer;
+ * use nlpo3::tokenizer::newmm::NewmmTokenizer;
+ * use tantivy_tokenizer_api::{TokenStream, Tokenizer};
+ *
+ * let words = vec!["กิน".to_string(), "ข้าว".to_string()];
+ * let tokenizer = NewmmTokenizer::from_word_list(words);
+ * let mut tokenizer = NewmmTantivyTokenizer::new(Arc::new(tokenizer));
+ * let mut stream = tokenizer.token_stream("กิน ข้าว");
+ * let mut texts = vec![];
+ * while stream.advance() {
+ *     texts.push(stream.token().text.clone());
+ * }
+ * assert_eq!(texts, ["กิน", "ข้าว"]);
+ * ```
+ *
+ * Token offsets are byte indices in the input text, as tantivy expects.
+ * Positions count the emitted tokens, so dropped whitespace
+ * does not leave a gap in phrase queries.
+*/
+use std::sync::Arc;
+
+use crate::tokenizer::newmm::NewmmTokenizer;
+use tantivy_tokenizer_api::{Token, TokenStream, Tokenizer};
+
+/// A tantivy `Tokenizer` backed by a [shared](NewmmTokenizer#sharing) `NewmmTokenizer`.
+#[derive(Clone, Debug)]
+pub struct NewmmTantivyTokenizer {
+    tokenizer: Arc<NewmmTokenizer>,
+    safe: bool,
+    keep_whitespace: bool,
+}
+
+impl NewmmTantivyTokenizer {
+    /// Create a tokenizer in safe mode, which drops whitespace tokens.
+    pub fn new(tokenizer: Arc<NewmmTokenizer>) -> Self {
+        Self {
+            tokenizer,
+            safe: true,
+            keep_whitespace: false,
+        }
+    }
+
+    /// Set [safe mode](NewmmTokenizer#safe-mode) (default: true).
+    pub fn safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Emit whitespace and line break tokens instead of dropping them (default: false).
+    pub fn keep_whitespace(mut self, keep_whitespace: bool) -> Self {
+        self.keep_whitespace = keep_whitespace;
+        self
+    }
+
+    /// Segment `text` into tantivy tokens.
+    ///
+    /// A text that cannot be segmented is emitted as a single token,
+    /// so it is still searchable.
+    fn tokens(&self, text: &str) -> Vec<Token> {
+        let segmented = match self.tokenizer.segment_to_byte_spans(text, self.safe, false) {
+            Ok(segmented) => segmented,
+            Err(_) => {
+                return vec![Token {
+                    offset_from: 0,
+                    offset_to: text.len(),
+                    position: 0,
+                    text: text.to_string(),
+                    position_length: 1,
+                }]
+            }
+        };
+        segmented
+            .into_iter()
+            .filter(|(token, _)| self.keep_whitespace || !token.text.trim().is_empty())
+            .enumerate()
+            .map(|(position, (token, span))| Token {
+                offset_from: span.start,
+                offset_to: span.end,
+                position,
+                text: token.text,
+                position_length: 1,
+            })
+            .collect()
+    }
+}
+
+impl Tokenizer for NewmmTantivyTokenizer {
+    type TokenStream<'a> = NewmmTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> NewmmTokenStream {
+        NewmmTokenStream {
+            tokens: self.tokens(text).into_iter(),
+            token: Token::default(),
+        }
+    }
+}
+
+/// Tokens of one text, segmented up front.
+pub struct NewmmTokenStream {
+    tokens: std::vec::IntoIter<Token>,
+    token: Token,
+}
+
+impl TokenStream for NewmmTokenStream {
+    fn advance(&mut self) -> bool {
+        match self.tokens.next() {
+            Some(token) => {
+                self.token = token;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+#[cfg(test)]
+fn collect_tokens(tokenizer: &mut NewmmTantivyTokenizer, text: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    tokenizer
+        .token_stream(text)
+        .process(&mut |token| tokens.push(token.clone()));
+    tokens
+}
+
+#[test]
+fn test_offsets_and_positions() {
+    let words = vec!["กิน".to_string(), "ข้าว".to_string(), "ปลา".to_string()];
+    let mut tokenizer = NewmmTantivyTokenizer::new(Arc::new(NewmmTokenizer::from_word_list(words)));
+    let text = "กินข้าว กินปลา";
+    let tokens = collect_tokens(&mut tokenizer, text);
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect::<Vec<_>>(),
+        ["กิน", "ข้าว", "กิน", "ปลา"]
+    );
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| token.position)
+            .collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+    for token in tokens.iter() {
+        assert_eq!(&text[token.offset_from..token.offset_to], token.text);
+    }
+}
+
+#[test]
+fn test_keep_whitespace() {
+    let words = vec!["กิน".to_string(), "ข้าว".to_string()];
+    let mut tokenizer = NewmmTantivyTokenizer::new(Arc::new(NewmmTokenizer::from_word_list(words)))
+        .keep_whitespace(true);
+    let tokens = collect_tokens(&mut tokenizer, "กิน ข้าว");
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect::<Vec<_>>(),
+        ["กิน", " ", "ข้าว"]
+    );
+    assert_eq!(tokens[2].position, 2);
+    assert_eq!(tokens[2].offset_from, "กิน ".len());
+    assert!(collect_tokens(&mut tokenizer, "").is_empty());
+}
@@ -1,8 +1,16 @@
 // SPDX-FileCopyrightText: 2024 PyThaiNLP Project
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod config;
 mod dict_reader;
+pub mod fuzzy;
+mod layered_dict;
 pub mod newmm;
+pub mod shared;
 pub(crate) mod tcc;
+pub mod token;
 pub mod tokenizer_trait;
 mod trie_char;
+
+pub use dict_reader::{DictSource, WordInfo};
+pub use layered_dict::WordOrder;
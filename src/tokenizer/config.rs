@@ -0,0 +1,141 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Tokenizer configuration.
+ *
+ * With the "serde" feature, a configuration can be kept in a file
+ * (TOML, JSON, ...) to rebuild the same tokenizer in another program:
+ *
+ * ```toml
+ * safe = true
+ * add_words = ["โควิด"]
+ *
+ * [dictionary]
+ * file_path = "words_th.txt"
+ *
+ * # optional, rules not listed are turned on
+ * [normalizer]
+ * reduce_repeats = false
+ * sanitizer = { form = "nfkc" }
+ * ```
+*/
+use super::{dict_reader::DictSource, newmm::NewmmTokenizer};
+use crate::normalize::Normalizer;
+use anyhow::Result as AnyResult;
+
+/// How to build a `NewmmTokenizer` and how to segment with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct NewmmConfig {
+    pub dictionary: DictSource,
+    /// Words added to the dictionary after it is loaded
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub add_words: Vec<String>,
+    /// Words removed from the dictionary after `add_words`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub remove_words: Vec<String>,
+    /// `safe` argument of `segment`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub safe: bool,
+    /// `parallel` argument of `segment`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub parallel: bool,
+    /// Normalizer used before segmentation, none by default
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub normalizer: Option<Normalizer>,
+}
+
+impl NewmmConfig {
+    pub fn new(dictionary: DictSource) -> Self {
+        Self {
+            dictionary,
+            add_words: vec![],
+            remove_words: vec![],
+            safe: false,
+            parallel: false,
+            normalizer: None,
+        }
+    }
+
+    /// Create a tokenizer from this configuration.
+    pub fn build(&self) -> AnyResult<NewmmTokenizer> {
+        let mut tokenizer = NewmmTokenizer::from_dict_source(self.dictionary.clone())?;
+        let add_words: Vec<&str> = self.add_words.iter().map(|word| word.as_str()).collect();
+        tokenizer.add_word(&add_words);
+        let remove_words: Vec<&str> = self.remove_words.iter().map(|word| word.as_str()).collect();
+        tokenizer.remove_word(&remove_words);
+        tokenizer.set_normalizer(self.normalizer.clone());
+        Ok(tokenizer)
+    }
+}
+
+#[test]
+fn test_build() {
+    use super::tokenizer_trait::Tokenizer;
+
+    let mut config = NewmmConfig::new(DictSource::WordList(vec![
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+        "ข้าวผัด".to_string(),
+    ]));
+    config.add_words = vec!["กินข้าว".to_string()];
+    config.remove_words = vec!["ข้าวผัด".to_string()];
+    let tokenizer = config.build().unwrap();
+    assert_eq!(
+        tokenizer.segment("กินข้าวข้าวผัด", false, false).unwrap(),
+        ["กินข้าว", "ข้าว", "ผัด"]
+    );
+
+    config.normalizer = Some(Normalizer::new());
+    let tokenizer = config.build().unwrap();
+    assert_eq!(tokenizer.normalizer(), Some(&Normalizer::new()));
+    assert_eq!(
+        tokenizer.segment("กินข้าาาาว", false, false).unwrap(),
+        ["กินข้าว"]
+    );
+
+    let config = NewmmConfig::new(DictSource::FilePath("no/such/file.txt".into()));
+    assert!(config.build().is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use crate::four_bytes_str::sanitize::{Sanitizer, UnicodeForm};
+
+    let config: NewmmConfig =
+        serde_json::from_str(r#"{"dictionary": {"word_list": ["กิน", "ข้าว"]}, "safe": true}"#)
+            .unwrap();
+    let mut expected = NewmmConfig::new(DictSource::WordList(vec![
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+    ]));
+    expected.safe = true;
+    assert_eq!(config, expected);
+    assert_eq!(
+        serde_json::from_str::<NewmmConfig>(&serde_json::to_string(&config).unwrap()).unwrap(),
+        config
+    );
+    assert!(serde_json::from_str::<NewmmConfig>(
+        r#"{"dictionary": {"word_list": []}, "unknown": true}"#
+    )
+    .is_err());
+
+    let config: NewmmConfig = serde_json::from_str(
+        r#"{"dictionary": {"word_list": []}, "normalizer": {"reduce_repeats": false, "sanitizer": {"form": "nfkc"}}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.normalizer,
+        Some(
+            Normalizer::new()
+                .reduce_repeats(false)
+                .sanitizer(Some(Sanitizer::new().form(Some(UnicodeForm::Nfkc))))
+        )
+    );
+}
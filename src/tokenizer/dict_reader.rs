@@ -3,44 +3,224 @@
 
 /**
  * Dictionary reader.
+ *
+ * Dictionary files can be compressed with gzip or zstd, with the "gzip"
+ * or "zstd" feature. A file is decompressed if its name ends with ".gz"
+ * or ".zst", or if its content starts with the format's magic bytes.
 */
 use crate::four_bytes_str::custom_string::CustomString;
 
-use super::trie_char::TrieChar as Trie;
+use super::{layered_dict::LayeredDict, trie_char::TrieChar as Trie};
+#[cfg(feature = "zstd")]
+use anyhow::Context;
+use anyhow::Result as AnyResult;
+use std::io::prelude::*;
 use std::io::BufReader;
-use std::{error::Error, io::prelude::*};
-use std::{fs::File, path::PathBuf};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
 
+/// Where the words of a dictionary come from.
+///
+/// A source is a value that can be cloned, compared and, with the "serde"
+/// feature, kept in a configuration file, so it has no variant for a reader:
+/// use `NewmmTokenizer::from_reader` to read a dictionary from a stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum DictSource {
+    /// A text file, one word per line, possibly compressed
     FilePath(PathBuf),
+    /// A list of words
     WordList(Vec<String>),
+    /// The content of a text file, possibly compressed
+    Bytes(Vec<u8>),
 }
 
-pub fn create_dict_trie(source: DictSource) -> Result<Trie, Box<dyn Error>> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+impl Compression {
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    fn from_magic(head: &[u8]) -> Self {
+        if head.starts_with(GZIP_MAGIC) {
+            Compression::Gzip
+        } else if head.starts_with(ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// A reader of the decompressed content.
+/// Without a known compression, it is detected from the magic bytes.
+fn decompress<'r, R: BufRead + 'r>(
+    mut reader: R,
+    compression: Option<Compression>,
+) -> AnyResult<Box<dyn BufRead + 'r>> {
+    let compression = match compression {
+        Some(compression) => compression,
+        None => Compression::from_magic(reader.fill_buf()?),
+    };
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(reader),
+        ))),
+        #[cfg(not(feature = "gzip"))]
+        Compression::Gzip => {
+            anyhow::bail!("Reading a gzip dictionary requires the \"gzip\" feature")
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(BufReader::new(
+            ruzstd::decoding::StreamingDecoder::new(reader).context("Invalid zstd dictionary")?,
+        ))),
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => {
+            anyhow::bail!("Reading a zstd dictionary requires the \"zstd\" feature")
+        }
+    }
+}
+
+/// Metadata of a dictionary word, from the extended dictionary format.
+///
+/// A line of a dictionary file is a word, optionally followed by
+/// tab-separated fields: frequency, part-of-speech tag,
+/// and comma-separated flags. An empty field is left unset.
+/// If the fields are not valid metadata, the whole line is the word.
+/// Blank lines and lines starting with "#" are skipped.
+///
+/// ```text
+/// # word<TAB>frequency<TAB>POS<TAB>flags
+/// ข้าว
+/// กิน\t1200\tVACT
+/// นายกรัฐมนตรี\t\tNCMN\tnever_split
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct WordInfo {
+    pub frequency: Option<u64>,
+    /// Part-of-speech tag
+    pub pos: Option<String>,
+    /// Flag "never_split": when this word matches,
+    /// shorter dictionary words starting at the same position are not used
+    pub never_split: bool,
+    /// Flag "no_prefix": this word is not used as a segmentation candidate,
+    /// but it is still in the dictionary and its metadata can be queried
+    pub no_prefix: bool,
+}
+
+impl WordInfo {
+    pub fn has_flags(&self) -> bool {
+        self.never_split || self.no_prefix
+    }
+}
+
+/// Parse the tab-separated fields after a word,
+/// or None if they are not valid metadata.
+fn parse_word_info<'a, I: Iterator<Item = &'a str>>(mut fields: I) -> Option<WordInfo> {
+    let mut info = WordInfo::default();
+    if let Some(frequency) = fields.next().filter(|field| !field.is_empty()) {
+        info.frequency = Some(frequency.parse().ok()?);
+    }
+    if let Some(pos) = fields.next().filter(|field| !field.is_empty()) {
+        info.pos = Some(pos.to_string());
+    }
+    if let Some(flags) = fields.next() {
+        for flag in flags.split(',').map(|flag| flag.trim()) {
+            match flag {
+                "never_split" => info.never_split = true,
+                "no_prefix" => info.no_prefix = true,
+                "" => {}
+                _ => return None,
+            }
+        }
+    }
+    match fields.next() {
+        Some(_) => None,
+        None => Some(info),
+    }
+}
+
+/// Parse a line of a dictionary file into a word and its metadata.
+/// Returns None for a blank or comment line.
+///
+/// A line with a tab that is not followed by valid metadata is a plain line:
+/// the whole line is the word, as in dictionaries without metadata.
+fn parse_dict_line(line: &str) -> Option<(&str, WordInfo)> {
+    if line.trim_start().starts_with('#') {
+        return None;
+    }
+    let mut fields = line.split('\t').map(|field| field.trim());
+    let word = fields.next().unwrap_or_default();
+    if word.is_empty() {
+        return None;
+    }
+    match parse_word_info(fields) {
+        Some(info) => Some((word, info)),
+        None => Some((line.trim(), WordInfo::default())),
+    }
+}
+
+/// Read the lines of a dictionary file, in the plain or the extended format.
+pub fn read_dict_lines<R: BufRead>(reader: R, dict: &mut LayeredDict) -> AnyResult<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if let Some((word, info)) = parse_dict_line(&line) {
+            dict.add_with_info(&CustomString::new(word), info);
+        }
+    }
+    Ok(())
+}
+
+/// Read a dictionary file, plain or compressed.
+pub fn read_dict<R: BufRead>(reader: R, dict: &mut LayeredDict) -> AnyResult<()> {
+    read_dict_lines(decompress(reader, None)?, dict)
+}
+
+pub fn create_dict(source: DictSource) -> AnyResult<LayeredDict> {
+    let mut dict = LayeredDict::new(Trie::new(&[]));
     match source {
         DictSource::FilePath(file_path) => {
-            let file_reader = File::open(file_path.as_path());
-            match file_reader {
-                Ok(file) => {
-                    let mut reader = BufReader::new(file);
-                    let mut line = String::with_capacity(50);
-                    let mut dict: Vec<CustomString> = Vec::with_capacity(600);
-                    while reader.read_line(&mut line).unwrap() != 0 {
-                        dict.push(CustomString::new(&line));
-                        line.clear();
-                    }
-                    dict.shrink_to_fit();
-                    Ok(Trie::new(&dict))
-                }
-                Err(error) => Err(Box::from(error)),
-            }
+            let file = File::open(file_path.as_path())?;
+            let compression = Compression::from_extension(&file_path);
+            read_dict_lines(decompress(BufReader::new(file), compression)?, &mut dict)?;
+            Ok(dict)
+        }
+        DictSource::Bytes(bytes) => {
+            read_dict(bytes.as_slice(), &mut dict)?;
+            Ok(dict)
         }
         DictSource::WordList(word_list) => {
             let custom_word_list: Vec<CustomString> = word_list
                 .into_iter()
                 .map(|word| CustomString::new(&word))
                 .collect();
-            Ok(Trie::new(&custom_word_list))
+            Ok(LayeredDict::new(Trie::new(&custom_word_list)))
         }
     }
 }
@@ -54,7 +234,95 @@ fn test_trie() {
         "42".to_string(),
         "aง|.%".to_string(),
     ];
-    let trie = create_dict_trie(DictSource::WordList(test_word_list)).unwrap();
-    assert!(trie.contain(&CustomString::new("กาแฟ")));
-    assert_eq!(trie.amount_of_words(), 5);
+    let dict = create_dict(DictSource::WordList(test_word_list)).unwrap();
+    assert!(dict.contains(CustomString::new("กาแฟ").raw_content()));
+    assert!(!dict.contains(CustomString::new("กา").raw_content()));
+}
+
+#[test]
+fn test_read_dict_lines() {
+    let mut dict = LayeredDict::new(Trie::new(&[]));
+    let content = "# comment\nกิน\t1200\tVACT\n\n ข้าว \r\nนายก\t\t\tnever_split,no_prefix\n";
+    read_dict_lines(content.as_bytes(), &mut dict).unwrap();
+    let info = |word: &str| dict.word_info(CustomString::new(word).raw_content());
+    assert_eq!(
+        info("กิน"),
+        Some(WordInfo {
+            frequency: Some(1200),
+            pos: Some("VACT".to_string()),
+            ..WordInfo::default()
+        })
+    );
+    assert_eq!(info("ข้าว"), Some(WordInfo::default()));
+    assert_eq!(
+        info("นายก"),
+        Some(WordInfo {
+            never_split: true,
+            no_prefix: true,
+            ..WordInfo::default()
+        })
+    );
+    assert_eq!(info("# comment"), None);
+
+    // not followed by valid metadata, the whole line is the word
+    for plain in ["ไก่\tmany", "ไก่\t\t\tunknown_flag", "ไก่\t1\tN\t\textra"] {
+        let mut dict = LayeredDict::new(Trie::new(&[]));
+        read_dict_lines(plain.as_bytes(), &mut dict).unwrap();
+        let word = CustomString::new(plain);
+        assert_eq!(
+            dict.word_info(word.raw_content()),
+            Some(WordInfo::default())
+        );
+        assert!(!dict.contains(CustomString::new("ไก่").raw_content()));
+    }
+}
+
+#[test]
+fn test_compression_detection() {
+    assert_eq!(
+        Compression::from_extension(Path::new("words_th.txt.gz")),
+        Some(Compression::Gzip)
+    );
+    assert_eq!(
+        Compression::from_extension(Path::new("words_th.txt.zst")),
+        Some(Compression::Zstd)
+    );
+    assert_eq!(Compression::from_extension(Path::new("words_th.txt")), None);
+    assert_eq!(Compression::from_magic(&[0x1f, 0x8b, 8]), Compression::Gzip);
+    assert_eq!(
+        Compression::from_magic(&[0x28, 0xb5, 0x2f, 0xfd, 0]),
+        Compression::Zstd
+    );
+    assert_eq!(Compression::from_magic("กิน".as_bytes()), Compression::None);
+    assert_eq!(Compression::from_magic(&[]), Compression::None);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_dict() {
+    use flate2::{write::GzEncoder, Compression as GzLevel};
+
+    let mut encoder = GzEncoder::new(Vec::new(), GzLevel::default());
+    encoder.write_all("กิน\nข้าว\n".as_bytes()).unwrap();
+    let dict = create_dict(DictSource::Bytes(encoder.finish().unwrap())).unwrap();
+    assert!(dict.contains(CustomString::new("ข้าว").raw_content()));
+    assert!(create_dict(DictSource::Bytes(vec![0x1f, 0x8b, 0])).is_err());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_dict() {
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+
+    let compressed = compress_to_vec("กิน\nข้าว\n".as_bytes(), CompressionLevel::Fastest);
+    let dict = create_dict(DictSource::Bytes(compressed)).unwrap();
+    assert!(dict.contains(CustomString::new("ข้าว").raw_content()));
+    assert!(create_dict(DictSource::Bytes(vec![0x28, 0xb5, 0x2f, 0xfd, 0])).is_err());
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn test_gzip_dict_without_feature() {
+    let error = create_dict(DictSource::Bytes(vec![0x1f, 0x8b, 0])).unwrap_err();
+    assert!(error.to_string().contains("\"gzip\" feature"));
 }
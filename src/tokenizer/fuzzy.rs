@@ -0,0 +1,229 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Approximate dictionary lookup, with a weighted edit distance.
+ *
+ * The distance between a query and a word is the lowest total cost of
+ * character insertions, deletions and substitutions turning one into the other
+ * (Levenshtein distance, with costs). Edits that are common in Thai spelling
+ * mistakes and OCR errors cost less: tone marks, and consonants with the same
+ * sound, such as ศ, ษ and ส.
+ *
+ * The dictionary trie is searched depth first, one row of the distance matrix
+ * per character, and a branch is left as soon as every cell of its row is
+ * over the maximum distance.
+*/
+use super::trie_char::TrieChar as Trie;
+
+/// Groups of consonants with the same sound, often confused in spelling.
+const SIMILAR_CONSONANTS: &[&str] = &[
+    "ขฃคฅฆ",
+    "ชฌ",
+    "ซศษส",
+    "ดฎ",
+    "ตฏ",
+    "ถฐ",
+    "ทธฑฒ",
+    "นณ",
+    "พภ",
+    "ฟฝ",
+    "ยญ",
+    "ลฬ",
+    "หฮ",
+];
+
+/// Mai ek, mai tho, mai tri and mai chattawa.
+fn is_tone_mark(character: char) -> bool {
+    ('\u{0E48}'..='\u{0E4B}').contains(&character)
+}
+
+fn is_similar_consonant(a: char, b: char) -> bool {
+    SIMILAR_CONSONANTS
+        .iter()
+        .any(|group| group.contains(a) && group.contains(b))
+}
+
+/// Costs of the edits of a fuzzy lookup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditCosts {
+    pub insertion: f32,
+    pub deletion: f32,
+    pub substitution: f32,
+    /// Cost of inserting, deleting or substituting a tone mark
+    pub tone_mark: f32,
+    /// Cost of substituting a consonant with one of the same sound
+    pub similar_consonant: f32,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        Self {
+            insertion: 1.0,
+            deletion: 1.0,
+            substitution: 1.0,
+            tone_mark: 0.5,
+            similar_consonant: 0.5,
+        }
+    }
+}
+
+impl EditCosts {
+    /// Costs of the plain Levenshtein distance, without Thai-specific edits.
+    pub fn uniform() -> Self {
+        Self {
+            insertion: 1.0,
+            deletion: 1.0,
+            substitution: 1.0,
+            tone_mark: 1.0,
+            similar_consonant: 1.0,
+        }
+    }
+
+    fn insertion_cost(&self, character: char) -> f32 {
+        if is_tone_mark(character) {
+            self.tone_mark
+        } else {
+            self.insertion
+        }
+    }
+
+    fn deletion_cost(&self, character: char) -> f32 {
+        if is_tone_mark(character) {
+            self.tone_mark
+        } else {
+            self.deletion
+        }
+    }
+
+    fn substitution_cost(&self, from: char, to: char) -> f32 {
+        if from == to {
+            0.0
+        } else if is_tone_mark(from) && is_tone_mark(to) {
+            self.tone_mark
+        } else if is_similar_consonant(from, to) {
+            self.similar_consonant
+        } else {
+            self.substitution
+        }
+    }
+
+    /// Weighted edit distance from `from` to `to`.
+    pub fn distance(&self, from: &str, to: &str) -> f32 {
+        let from: Vec<char> = from.chars().collect();
+        let mut row = self.first_row(&from);
+        for character in to.chars() {
+            row = self.next_row(&from, &row, character);
+        }
+        row[from.len()]
+    }
+
+    /// Distances from the prefixes of `query` to an empty word.
+    fn first_row(&self, query: &[char]) -> Vec<f32> {
+        let mut row = Vec::with_capacity(query.len() + 1);
+        row.push(0.0);
+        for (i, &character) in query.iter().enumerate() {
+            row.push(row[i] + self.deletion_cost(character));
+        }
+        row
+    }
+
+    /// Distances from the prefixes of `query` to a word,
+    /// given the distances to the word without its last character.
+    fn next_row(&self, query: &[char], previous: &[f32], character: char) -> Vec<f32> {
+        let mut row = Vec::with_capacity(previous.len());
+        row.push(previous[0] + self.insertion_cost(character));
+        for (i, &query_character) in query.iter().enumerate() {
+            let cost = (previous[i + 1] + self.insertion_cost(character))
+                .min(row[i] + self.deletion_cost(query_character))
+                .min(previous[i] + self.substitution_cost(query_character, character));
+            row.push(cost);
+        }
+        row
+    }
+}
+
+/// A dictionary word found by a fuzzy lookup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzyMatch {
+    pub word: String,
+    /// Weighted edit distance from the query to the word
+    pub distance: f32,
+}
+
+/// Words of a trie within `max_distance` of `query`, in no particular order.
+pub(crate) fn search(
+    trie: &Trie,
+    query: &str,
+    max_distance: f32,
+    costs: &EditCosts,
+) -> Vec<FuzzyMatch> {
+    let query: Vec<char> = query.chars().collect();
+    // rows[depth] holds the distances to the word of the current path,
+    // cut to `depth` characters
+    let mut rows = vec![costs.first_row(&query)];
+    let mut matches = vec![];
+    trie.visit(|path, end| {
+        rows.truncate(path.len());
+        let row = costs.next_row(&query, &rows[path.len() - 1], path[path.len() - 1]);
+        let distance = row[query.len()];
+        if end && distance <= max_distance {
+            matches.push(FuzzyMatch {
+                word: path.iter().collect(),
+                distance,
+            });
+        }
+        // distances only grow along a branch
+        let descend = row.iter().any(|&cost| cost <= max_distance);
+        rows.push(row);
+        descend
+    });
+    matches
+}
+
+#[test]
+fn test_distance() {
+    let costs = EditCosts::default();
+    assert_eq!(costs.distance("ศาลา", "ศาลา"), 0.0);
+    assert_eq!(costs.distance("ศาลา", "ศาลาวัด"), 3.0);
+    assert_eq!(costs.distance("ศาลาวัด", "ศาลา"), 3.0);
+    assert_eq!(costs.distance("ไก", "ไก่"), 0.5);
+    assert_eq!(costs.distance("ไก้", "ไก่"), 0.5);
+    assert_eq!(costs.distance("สาลา", "ศาลา"), 0.5);
+    assert_eq!(costs.distance("กาลา", "ศาลา"), 1.0);
+    assert_eq!(EditCosts::uniform().distance("ไก้", "ไก่"), 1.0);
+}
+
+#[test]
+fn test_search() {
+    use crate::four_bytes_str::custom_string::CustomString;
+
+    let trie = Trie::new(&[
+        CustomString::new("ศาลา"),
+        CustomString::new("ศาล"),
+        CustomString::new("ศาสนา"),
+        CustomString::new("ไก่"),
+    ]);
+    let costs = EditCosts::default();
+    assert_eq!(
+        search(&trie, "สาลา", 1.0, &costs),
+        [FuzzyMatch {
+            word: "ศาลา".to_string(),
+            distance: 0.5,
+        }]
+    );
+    let mut words: Vec<String> = search(&trie, "สาลา", 1.5, &costs)
+        .into_iter()
+        .map(|found| found.word)
+        .collect();
+    words.sort();
+    assert_eq!(words, ["ศาล", "ศาลา"]);
+    assert_eq!(
+        search(&trie, "ไก้", 0.5, &costs),
+        [FuzzyMatch {
+            word: "ไก่".to_string(),
+            distance: 0.5,
+        }]
+    );
+    assert!(search(&trie, "ปลา", 1.0, &costs).is_empty());
+}
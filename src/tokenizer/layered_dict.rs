@@ -0,0 +1,401 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * A dictionary made of a stack of layers.
+ *
+ * The bottom layer is usually a large base dictionary, shared between
+ * tokenizers behind an `Arc`. Each layer above it adds words and suppresses
+ * words of the layers below it. A word is in the dictionary if the topmost
+ * layer that mentions it adds it.
+ *
+ * Only the top layer is modified, and it is copied first if it is shared
+ * (copy-on-write). A shared bottom layer is never copied: a new layer
+ * is pushed on top of it instead, so the layer count stays small.
+*/
+use std::sync::Arc;
+
+use super::{
+    dict_reader::WordInfo,
+    fuzzy::{self, EditCosts, FuzzyMatch},
+    trie_char::TrieChar as Trie,
+};
+use crate::four_bytes_str::custom_string::{
+    CustomString, CustomStringBytesSlice, CustomStringBytesVec,
+};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+#[derive(Clone, Debug)]
+struct DictLayer {
+    added: Trie,
+    /// Words of the layers below that this layer suppresses
+    removed: HashSet<CustomStringBytesVec>,
+    /// Metadata of the added words that have any
+    infos: HashMap<CustomStringBytesVec, WordInfo>,
+    /// Added words with a flag, which changes segmentation
+    flagged: HashSet<CustomStringBytesVec>,
+}
+
+impl DictLayer {
+    fn new(added: Trie) -> Self {
+        Self {
+            added,
+            removed: HashSet::default(),
+            infos: HashMap::default(),
+            flagged: HashSet::default(),
+        }
+    }
+}
+
+/// Order of the words returned by dictionary queries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WordOrder {
+    /// Unicode code point order
+    #[default]
+    Alphabetical,
+    /// Highest frequency first, then alphabetical;
+    /// words without a frequency come last
+    Frequency,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct LayeredDict {
+    /// Layers, from bottom to top; never empty
+    layers: Vec<Arc<DictLayer>>,
+}
+
+impl LayeredDict {
+    pub fn new(base: Trie) -> Self {
+        Self {
+            layers: vec![Arc::new(DictLayer::new(base))],
+        }
+    }
+
+    /// A dictionary sharing all layers of this one, with an empty layer on top.
+    pub fn with_overlay(&self) -> Self {
+        let mut layers = self.layers.clone();
+        layers.push(Arc::new(DictLayer::new(Trie::new(&[]))));
+        Self { layers }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// The top layer, made unshared.
+    fn top_mut(&mut self) -> &mut DictLayer {
+        let top_is_shared = Arc::get_mut(self.layers.last_mut().unwrap()).is_none();
+        if top_is_shared && self.layers.len() == 1 {
+            self.layers.push(Arc::new(DictLayer::new(Trie::new(&[]))));
+        }
+        Arc::make_mut(self.layers.last_mut().unwrap())
+    }
+
+    pub fn add(&mut self, word: &CustomString) {
+        self.add_with_info(word, WordInfo::default());
+    }
+
+    pub fn add_with_info(&mut self, word: &CustomString, info: WordInfo) {
+        let word = word.trim();
+        if word.is_empty() {
+            return;
+        }
+        let top = self.top_mut();
+        let raw_word = word.raw_content();
+        top.removed.remove(raw_word);
+        top.added.add(&word);
+        if info.has_flags() {
+            top.flagged.insert(raw_word.into());
+        } else {
+            top.flagged.remove(raw_word);
+        }
+        if info == WordInfo::default() {
+            top.infos.remove(raw_word);
+        } else {
+            top.infos.insert(raw_word.into(), info);
+        }
+    }
+
+    pub fn remove(&mut self, word: &CustomString) {
+        let word = word.trim();
+        if word.is_empty() {
+            return;
+        }
+        let top = self.top_mut();
+        top.added.remove(&word);
+        top.infos.remove(word.raw_content());
+        top.flagged.remove(word.raw_content());
+        // layers below may be shared, so their words are suppressed instead
+        let (top, layers_below) = self.layers.split_last_mut().unwrap();
+        if layers_below.iter().any(|layer| layer.added.contain(&word)) {
+            Arc::get_mut(top)
+                .unwrap()
+                .removed
+                .insert(word.raw_content().into());
+        }
+    }
+
+    pub fn contains(&self, word: &CustomStringBytesSlice) -> bool {
+        for layer in self.layers.iter().rev() {
+            if layer.removed.contains(word) {
+                return false;
+            }
+            if layer.added.contain_raw(word) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Metadata of a word, or None if it is not in the dictionary.
+    pub fn word_info(&self, word: &CustomStringBytesSlice) -> Option<WordInfo> {
+        for layer in self.layers.iter().rev() {
+            if layer.removed.contains(word) {
+                return None;
+            }
+            if layer.added.contain_raw(word) {
+                return Some(layer.infos.get(word).cloned().unwrap_or_default());
+            }
+        }
+        None
+    }
+
+    fn has_flags(&self) -> bool {
+        self.layers.iter().any(|layer| !layer.flagged.is_empty())
+    }
+
+    /// Metadata of a word, if it is in the dictionary and has any.
+    fn info_ref(&self, word: &CustomStringBytesSlice) -> Option<&WordInfo> {
+        for layer in self.layers.iter().rev() {
+            if layer.removed.contains(word) {
+                return None;
+            }
+            if layer.added.contain_raw(word) {
+                return layer.infos.get(word);
+            }
+        }
+        None
+    }
+
+    /// Apply word flags to the prefixes found at one position.
+    fn apply_flags(&self, prefixes: &mut Vec<&CustomStringBytesSlice>) {
+        let never_split = prefixes
+            .iter()
+            .rposition(|word| self.info_ref(word).is_some_and(|info| info.never_split));
+        if let Some(never_split) = never_split {
+            prefixes.drain(..never_split);
+        }
+        prefixes.retain(|word| !self.info_ref(word).is_some_and(|info| info.no_prefix));
+    }
+
+    /// Words starting with `prefix`, at most `limit` of them.
+    pub fn words_with_prefix(
+        &self,
+        prefix: &CustomString,
+        limit: Option<usize>,
+        order: WordOrder,
+    ) -> Vec<String> {
+        // alphabetical words are walked in order, so the walk can stop early
+        let walk_limit = match order {
+            WordOrder::Alphabetical => limit,
+            WordOrder::Frequency => None,
+        };
+        let mut words: Vec<String> = if self.layers.len() > 1 {
+            // the first words of the dictionary are among the first words
+            // of the layers that add them
+            let mut words: Vec<String> = self
+                .layers
+                .iter()
+                .flat_map(|layer| {
+                    layer
+                        .added
+                        .words_with_prefix_filtered(prefix, walk_limit, |word| {
+                            self.contains(CustomString::new(word).raw_content())
+                        })
+                })
+                .collect();
+            words.sort_unstable();
+            words.dedup();
+            words
+        } else {
+            self.layers[0].added.words_with_prefix(prefix, walk_limit)
+        };
+        if order == WordOrder::Frequency {
+            let frequency = |word: &String| {
+                self.info_ref(CustomString::new(word).raw_content())
+                    .and_then(|info| info.frequency)
+            };
+            // stable sort, words of the same frequency stay alphabetical
+            words.sort_by_cached_key(|word| std::cmp::Reverse(frequency(word)));
+        }
+        if let Some(limit) = limit {
+            words.truncate(limit);
+        }
+        words
+    }
+
+    /// Words within `max_distance` of `query`, closest first.
+    pub fn fuzzy_search(
+        &self,
+        query: &str,
+        max_distance: f32,
+        costs: &EditCosts,
+    ) -> Vec<FuzzyMatch> {
+        let mut matches: Vec<FuzzyMatch> = self
+            .layers
+            .iter()
+            .flat_map(|layer| fuzzy::search(&layer.added, query, max_distance, costs))
+            .collect();
+        matches.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        if self.layers.len() > 1 {
+            matches.dedup_by(|a, b| a.word == b.word);
+            matches.retain(|found| self.contains(CustomString::new(&found.word).raw_content()));
+        }
+        matches
+    }
+
+    /// Returns a vec of prefixes of `prefix` (as reference) that are words
+    /// in the dictionary, from the shortest to the longest.
+    pub fn prefix_ref<'p>(&self, prefix: &'p CustomString) -> Vec<&'p CustomStringBytesSlice> {
+        if self.layers.len() == 1 {
+            let mut result = Trie::prefix_ref(prefix, &self.layers[0].added);
+            if self.has_flags() {
+                self.apply_flags(&mut result);
+            }
+            return result;
+        }
+        let mut result: Vec<&CustomStringBytesSlice> = self
+            .layers
+            .iter()
+            .flat_map(|layer| Trie::prefix_ref(prefix, &layer.added))
+            .collect();
+        // prefixes of the same text are equal if they have the same length
+        result.sort_unstable_by_key(|word| word.len());
+        result.dedup_by_key(|word| word.len());
+        result.retain(|word| self.contains(word));
+        if self.has_flags() {
+            self.apply_flags(&mut result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+fn words(dict: &LayeredDict, text: &str) -> Vec<String> {
+    let text = CustomString::new(text);
+    dict.prefix_ref(&text)
+        .into_iter()
+        .map(CustomString::convert_raw_bytes_to_std_string)
+        .collect()
+}
+
+#[test]
+fn test_overlays() {
+    let base = LayeredDict::new(Trie::new(&[
+        CustomString::new("ศาล"),
+        CustomString::new("ศาลา"),
+    ]));
+    let mut overlay = base.with_overlay();
+    overlay.add(&CustomString::new("ศา"));
+    overlay.remove(&CustomString::new("ศาลา"));
+    assert_eq!(words(&overlay, "ศาลาวัด"), ["ศา", "ศาล"]);
+    assert_eq!(words(&base, "ศาลาวัด"), ["ศาล", "ศาลา"]);
+
+    // a word suppressed in a lower layer can be added back
+    let mut top = overlay.with_overlay();
+    top.add(&CustomString::new("ศาลา"));
+    assert_eq!(words(&top, "ศาลาวัด"), ["ศา", "ศาล", "ศาลา"]);
+    assert_eq!(top.layer_count(), 3);
+}
+
+#[test]
+fn test_shared_layer_is_not_modified() {
+    let mut base = LayeredDict::new(Trie::new(&[CustomString::new("ศาล")]));
+    base.add(&CustomString::new("ศาลา"));
+    assert_eq!(base.layer_count(), 1);
+
+    let overlay = base.with_overlay();
+    base.remove(&CustomString::new("ศาล"));
+    assert_eq!(base.layer_count(), 2);
+    assert_eq!(words(&base, "ศาลา"), ["ศาลา"]);
+    assert_eq!(words(&overlay, "ศาลา"), ["ศาล", "ศาลา"]);
+
+    // a shared overlay is copied, instead of adding a layer
+    let mut copy = base.clone();
+    copy.add(&CustomString::new("ศา"));
+    assert_eq!(copy.layer_count(), 2);
+    assert_eq!(words(&copy, "ศาลา"), ["ศา", "ศาลา"]);
+    assert_eq!(words(&base, "ศาลา"), ["ศาลา"]);
+}
+
+#[test]
+fn test_flags() {
+    let mut dict = LayeredDict::new(Trie::new(&[
+        CustomString::new("ศา"),
+        CustomString::new("ศาล"),
+        CustomString::new("ศาลาวัด"),
+    ]));
+    dict.add_with_info(
+        &CustomString::new("ศาลา"),
+        WordInfo {
+            never_split: true,
+            ..WordInfo::default()
+        },
+    );
+    assert_eq!(words(&dict, "ศาลาวัด"), ["ศาลา", "ศาลาวัด"]);
+
+    dict.add_with_info(
+        &CustomString::new("ศาลาวัด"),
+        WordInfo {
+            no_prefix: true,
+            ..WordInfo::default()
+        },
+    );
+    assert_eq!(words(&dict, "ศาลาวัด"), ["ศาลา"]);
+    assert!(dict.contains(CustomString::new("ศาลาวัด").raw_content()));
+
+    // adding a word again without metadata clears its flags
+    dict.add(&CustomString::new("ศาลา"));
+    dict.add(&CustomString::new("ศาลาวัด"));
+    assert_eq!(words(&dict, "ศาลาวัด"), ["ศา", "ศาล", "ศาลา", "ศาลาวัด"]);
+}
+
+#[test]
+fn test_words_with_prefix() {
+    let mut dict = LayeredDict::new(Trie::new(&[
+        CustomString::new("ศาล"),
+        CustomString::new("ศาลา"),
+        CustomString::new("ศาสนา"),
+    ]))
+    .with_overlay();
+    dict.remove(&CustomString::new("ศาลา"));
+    dict.add_with_info(
+        &CustomString::new("ศาสตร์"),
+        WordInfo {
+            frequency: Some(10),
+            ..WordInfo::default()
+        },
+    );
+    let prefix = CustomString::new("ศา");
+    assert_eq!(
+        dict.words_with_prefix(&prefix, None, WordOrder::Alphabetical),
+        ["ศาล", "ศาสตร์", "ศาสนา"]
+    );
+    assert_eq!(
+        dict.words_with_prefix(&prefix, Some(2), WordOrder::Frequency),
+        ["ศาสตร์", "ศาล"]
+    );
+    // the first words in alphabetical order, skipping the removed word
+    assert_eq!(
+        dict.words_with_prefix(&prefix, Some(2), WordOrder::Alphabetical),
+        ["ศาล", "ศาสตร์"]
+    );
+    assert_eq!(
+        dict.words_with_prefix(&CustomString::new(""), Some(1), WordOrder::Alphabetical),
+        ["ศาล"]
+    );
+}
@@ -14,11 +14,22 @@
  * 
  * Rust implementation: ["Thanathip Suntorntip"]
 */
-use std::{collections::VecDeque, error::Error, fmt::Display, path::PathBuf};
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::Display,
+    io::BufRead,
+    ops::Range,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use super::{
-    dict_reader::{create_dict_trie, DictSource},
+    dict_reader::{create_dict, read_dict, DictSource, WordInfo},
+    fuzzy::{EditCosts, FuzzyMatch},
+    layered_dict::{LayeredDict, WordOrder},
     tcc::tcc_tokenizer,
+    token::{Token, TokenKind},
     tokenizer_trait::Tokenizer,
     trie_char::TrieChar as Trie,
 };
@@ -26,14 +37,17 @@ use crate::four_bytes_str::custom_string::{CustomStringBytesSlice, FixedCharsLen
 
 use crate::four_bytes_str::custom_regex::regex_pattern_to_custom_pattern;
 use crate::four_bytes_str::custom_string::{rfind_space_char_index, CustomString, BYTES_PER_CHAR};
+use crate::normalize::Normalizer;
 use anyhow::Result as AnyResult;
 use binary_heap_plus::{BinaryHeap, MinComparator};
 use lazy_static::lazy_static;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use regex::bytes::Regex;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 const MAX_GRAPH_SIZE: usize = 50;
+#[cfg(feature = "parallel")]
 const USE_MULTITHREAD_THRESHOLD: usize = 10000;
 
 // window size to check break points, for safe mode
@@ -100,28 +114,165 @@ impl Display for BFSSearchError {
 
 impl Error for BFSSearchError {}
 
-#[derive(Debug)]
+/// Error of a segmentation stopped by its cancel flag,
+/// see `NewmmTokenizer::segment_to_tokens_cancellable`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SegmentCancelled;
+
+impl Display for SegmentCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Segmentation was cancelled")
+    }
+}
+
+impl Error for SegmentCancelled {}
+
+/// Words passing `filter`, filtered on multiple threads if there are many of them.
+fn filter_words<'a, F>(words: Vec<&'a [u8]>, filter: F) -> Vec<&'a [u8]>
+where
+    F: Fn(&&'a [u8]) -> bool + Sync + Send,
+{
+    match words.len() {
+        #[cfg(feature = "parallel")]
+        length if length >= USE_MULTITHREAD_THRESHOLD => {
+            words.into_par_iter().filter(filter).collect()
+        }
+        _ => words.into_iter().filter(filter).collect(),
+    }
+}
+
+fn check_cancelled(cancelled: Option<&AtomicBool>) -> AnyResult<()> {
+    match cancelled {
+        Some(cancelled) if cancelled.load(Ordering::Relaxed) => Err(SegmentCancelled.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Intermediate results recorded by `traced_one_cut`.
+#[derive(Default)]
+struct CutTrace {
+    tcc_boundaries: HashSet<CharacterIndex>,
+    graph: HashMap<CharacterIndex, Vec<CharacterIndex>>,
+    /// start of each token that went through the unknown-word path -> its kind
+    unknown_kinds: HashMap<CharacterIndex, TokenKind>,
+}
+
+/// How a text was segmented, for debugging a dictionary.
+///
+/// All positions are character indices.
+#[derive(Clone, Debug)]
+pub struct SegmentTrace {
+    /// Resulting tokens, with how each of them was found
+    pub tokens: Vec<Token>,
+    /// End positions of Thai Character Clusters (TCCs), in ascending order
+    pub tcc_boundaries: Vec<usize>,
+    /// Edges (begin, end) of the candidate word graph, in ascending order
+    pub graph: Vec<(usize, usize)>,
+}
+
+/// Dictionary-based maximal matching word tokenizer.
+///
+/// The dictionary is a stack of layers, which are shared
+/// by clones of the tokenizer and by tokenizers from `with_overlay`.
+/// `add_word` and `remove_word` modify the top layer, copying it first
+/// if it is shared; a shared base dictionary is never copied.
+///
+/// With a `Normalizer` (see `set_normalizer`), text is normalized
+/// before it is segmented.
+///
+/// # Safe mode
+///
+/// With `safe` set, a long text is cut into parts of about 120 characters
+/// at likely word boundaries, and the parts are segmented separately.
+/// This avoids long run times on ambiguous text, at the cost of
+/// a possibly different cut near the boundaries of the parts.
+///
+/// # Sharing
+///
+/// Wrappers such as `SpellChecker` and `Romanizer` hold an `Arc<NewmmTokenizer>`:
+/// cloning them, or creating them with the same `Arc`, shares the tokenizer
+/// and its dictionary instead of copying them.
+#[derive(Clone, Debug)]
 pub struct NewmmTokenizer {
-    dict: Box<Trie>,
+    dict: LayeredDict,
+    normalizer: Option<Normalizer>,
 }
 
 impl NewmmTokenizer {
-    /// Create a new tokenizer using a dictionary from a text file
+    /// Create a new tokenizer using a dictionary from a text file,
+    /// one word per line, or in the extended format described in `WordInfo`
     pub fn new(dict_path: &str) -> Self {
         NewmmTokenizer {
-            dict: Box::from(
-                create_dict_trie(DictSource::FilePath(PathBuf::from(dict_path))).unwrap(),
-            ),
+            dict: create_dict(DictSource::FilePath(PathBuf::from(dict_path))).unwrap(),
+            normalizer: None,
         }
     }
 
     /// Create a new tokenizer using a dictionary from a vector of Strings
     pub fn from_word_list(word_list: Vec<String>) -> Self {
         NewmmTokenizer {
-            dict: Box::from(create_dict_trie(DictSource::WordList(word_list)).unwrap()),
+            dict: create_dict(DictSource::WordList(word_list)).unwrap(),
+            normalizer: None,
         }
     }
 
+    /// Create a new tokenizer using a dictionary from a file or a list of words.
+    /// Unlike `new`, an unreadable file is an error instead of a panic.
+    pub fn from_dict_source(source: DictSource) -> AnyResult<Self> {
+        Ok(NewmmTokenizer {
+            dict: create_dict(source)?,
+            normalizer: None,
+        })
+    }
+
+    /// Create a new tokenizer using a dictionary from the bytes of a text file,
+    /// one word per line, in UTF-8.
+    /// The bytes can be compressed with gzip or zstd, with the matching feature.
+    pub fn from_bytes(dict_bytes: &[u8]) -> AnyResult<Self> {
+        Self::from_reader(dict_bytes)
+    }
+
+    /// Create a new tokenizer using a dictionary read from a text file,
+    /// a compressed file, or any other reader, without a temporary file
+    pub fn from_reader<R: BufRead>(reader: R) -> AnyResult<Self> {
+        let mut dict = LayeredDict::new(Trie::new(&[]));
+        read_dict(reader, &mut dict)?;
+        Ok(NewmmTokenizer {
+            dict,
+            normalizer: None,
+        })
+    }
+
+    /// Create a tokenizer sharing this tokenizer's dictionary,
+    /// with an empty layer on top for its own added and removed words.
+    ///
+    /// For example, a large base dictionary loaded once
+    /// and small per-domain word lists on top of it.
+    pub fn with_overlay(&self) -> Self {
+        NewmmTokenizer {
+            dict: self.dict.with_overlay(),
+            normalizer: self.normalizer.clone(),
+        }
+    }
+
+    /// Normalize text before segmenting it, or not with `None` (the default).
+    ///
+    /// Segmented words are slices of the normalized text,
+    /// and the positions of tokens are in the original text.
+    pub fn set_normalizer(&mut self, normalizer: Option<Normalizer>) {
+        self.normalizer = normalizer;
+    }
+
+    /// The normalizer used before segmentation, if any
+    pub fn normalizer(&self) -> Option<&Normalizer> {
+        self.normalizer.as_ref()
+    }
+
+    /// Number of layers in the tokenizer's dictionary
+    pub fn dict_layer_count(&self) -> usize {
+        self.dict.layer_count()
+    }
+
     /// Add words to the tokenizer's dictionary
     pub fn add_word(&mut self, word_list: &[&str]) {
         for word in word_list {
@@ -129,13 +280,262 @@ impl NewmmTokenizer {
         }
     }
 
-    /// Remove words from the tokenizer's dictionary
+    /// Add a word with its metadata to the tokenizer's dictionary.
+    /// Adding a word again replaces its metadata.
+    pub fn add_word_with_info(&mut self, word: &str, info: WordInfo) {
+        self.dict.add_with_info(&CustomString::new(word), info);
+    }
+
+    /// Metadata of a word, or None if it is not in the tokenizer's dictionary.
+    /// A word without metadata has the default `WordInfo`.
+    pub fn word_info(&self, word: &str) -> Option<WordInfo> {
+        self.dict
+            .word_info(CustomString::new(word).trim().raw_content())
+    }
+
+    /// Whether a word is in the tokenizer's dictionary
+    pub fn contains_word(&self, word: &str) -> bool {
+        self.dict
+            .contains(CustomString::new(word).trim().raw_content())
+    }
+
+    /// Dictionary words starting with `prefix`, at most `limit` of them,
+    /// for example to autocomplete a search box.
+    /// An empty prefix matches every word.
+    pub fn words_with_prefix(
+        &self,
+        prefix: &str,
+        limit: Option<usize>,
+        order: WordOrder,
+    ) -> Vec<String> {
+        self.dict
+            .words_with_prefix(&CustomString::new(prefix).trim(), limit, order)
+    }
+
+    /// Dictionary words within `max_distance` of `query`, closest first,
+    /// for example to suggest corrections of a misspelled word.
+    /// With the default `EditCosts`, tone mark edits and substitutions of
+    /// consonants with the same sound cost 0.5, other edits cost 1.
+    pub fn fuzzy_lookup(
+        &self,
+        query: &str,
+        max_distance: f32,
+        costs: &EditCosts,
+    ) -> Vec<FuzzyMatch> {
+        let query = CustomString::new(query).trim();
+        self.dict.fuzzy_search(
+            &CustomString::convert_raw_bytes_to_std_string(query.raw_content()),
+            max_distance,
+            costs,
+        )
+    }
+
+    /// All words of the tokenizer's dictionary, in Unicode code point order
+    pub fn words(&self) -> impl Iterator<Item = String> {
+        self.words_with_prefix("", None, WordOrder::Alphabetical)
+            .into_iter()
+    }
+
+    /// Remove words from the tokenizer's dictionary.
+    /// Words of a shared layer are suppressed, not removed from that layer.
     pub fn remove_word(&mut self, word_list: &[&str]) {
         for word in word_list {
             self.dict.remove(&CustomString::new(word));
         }
     }
 
+    /// Segment a text (without safe mode) and return the intermediate results:
+    /// TCC boundaries, candidate word graph, and tokens from the unknown-word path.
+    ///
+    /// With a normalizer, the normalized text is segmented, and positions are
+    /// mapped to `text`, as with `segment_to_tokens`.
+    pub fn trace(&self, text: &str) -> AnyResult<SegmentTrace> {
+        let normalized = self
+            .normalizer
+            .as_ref()
+            .map(|normalizer| normalizer.normalize(text));
+        let input = CustomString::new(normalized.as_ref().map_or(text, |text| text.as_str()));
+        let mut cut_trace = CutTrace::default();
+        let words = Self::traced_one_cut(&input, &self.dict, Some(&mut cut_trace), None)?;
+        // positions in the normalized text, mapped to the original text
+        let original = |position: usize| match &normalized {
+            Some(normalized) => normalized.original_position(position),
+            None => position,
+        };
+
+        let mut tokens = Self::to_tokens(&words, &cut_trace, 0);
+        for token in tokens.iter_mut() {
+            token.start = original(token.start);
+            token.end = original(token.end);
+        }
+
+        let mut tcc_boundaries: Vec<usize> =
+            cut_trace.tcc_boundaries.into_iter().map(original).collect();
+        tcc_boundaries.sort_unstable();
+        tcc_boundaries.dedup();
+        let mut graph: Vec<(usize, usize)> = cut_trace
+            .graph
+            .into_iter()
+            .flat_map(|(begin, ends)| ends.into_iter().map(move |end| (begin, end)))
+            .map(|(begin, end)| (original(begin), original(end)))
+            .collect();
+        graph.sort_unstable();
+        graph.dedup();
+
+        Ok(SegmentTrace {
+            tokens,
+            tcc_boundaries,
+            graph,
+        })
+    }
+
+    /// Segment a text into tokens, with their positions
+    /// and whether they are dictionary words.
+    ///
+    /// With a normalizer, the text of a token is normalized,
+    /// and its position is the span it comes from in `text`.
+    pub fn segment_to_tokens(
+        &self,
+        text: &str,
+        safe: bool,
+        parallel: bool,
+    ) -> AnyResult<Vec<Token>> {
+        self.cancellable_tokens(text, safe, parallel, None)
+    }
+
+    /// Same as `segment_to_tokens`, but stops with a `SegmentCancelled` error
+    /// once `cancelled` is set, for example by another thread.
+    ///
+    /// The flag is checked at each position where a word can start,
+    /// so a long text stops soon after the flag is set, with or without safe mode.
+    /// Tokens are the same as those of `segment_to_tokens`.
+    pub fn segment_to_tokens_cancellable(
+        &self,
+        text: &str,
+        safe: bool,
+        parallel: bool,
+        cancelled: &AtomicBool,
+    ) -> AnyResult<Vec<Token>> {
+        self.cancellable_tokens(text, safe, parallel, Some(cancelled))
+    }
+
+    /// Same as `segment_to_tokens`, with the byte range of each token in `text`,
+    /// as `start` and `end` of a `Token` are character indices.
+    pub fn segment_to_byte_spans(
+        &self,
+        text: &str,
+        safe: bool,
+        parallel: bool,
+    ) -> AnyResult<Vec<(Token, Range<usize>)>> {
+        let tokens = self.segment_to_tokens(text, safe, parallel)?;
+        // byte offset of each character index, and of the end of the text
+        let byte_offsets: Vec<usize> = text
+            .char_indices()
+            .map(|(byte_offset, _)| byte_offset)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        Ok(tokens
+            .into_iter()
+            .map(|token| {
+                let span = byte_offsets[token.start]..byte_offsets[token.end];
+                (token, span)
+            })
+            .collect())
+    }
+
+    fn cancellable_tokens(
+        &self,
+        text: &str,
+        safe: bool,
+        parallel: bool,
+        cancelled: Option<&AtomicBool>,
+    ) -> AnyResult<Vec<Token>> {
+        let normalizer = match &self.normalizer {
+            Some(normalizer) => normalizer,
+            None => return self.tokens_of(text, safe, parallel, cancelled),
+        };
+        let normalized = normalizer.normalize(text);
+        let mut tokens = self.tokens_of(normalized.as_str(), safe, parallel, cancelled)?;
+        for token in tokens.iter_mut() {
+            let (start, end) = normalized.original_span(token.start, token.end);
+            token.start = start;
+            token.end = end;
+        }
+        Ok(tokens)
+    }
+
+    /// Segment a text into tokens, without normalizing it.
+    #[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+    fn tokens_of(
+        &self,
+        text: &str,
+        safe: bool,
+        parallel: bool,
+        cancelled: Option<&AtomicBool>,
+    ) -> AnyResult<Vec<Token>> {
+        let input = CustomString::new(text);
+        if input.is_empty() {
+            return Ok(vec![]);
+        }
+        let parts = if !safe || input.chars_len() < TEXT_SCAN_END {
+            vec![input]
+        } else {
+            Self::split_safe_parts(&input, &self.dict, cancelled)?
+        };
+        let mut part_starts: Vec<CharacterIndex> = Vec::with_capacity(parts.len());
+        let mut part_start: CharacterIndex = 0;
+        for part in parts.iter() {
+            part_starts.push(part_start);
+            part_start += part.chars_len();
+        }
+
+        let cut_part = |(part, part_start): (&CustomString, &CharacterIndex)| -> AnyResult<_> {
+            let mut cut_trace = CutTrace::default();
+            let words = Self::traced_one_cut(part, &self.dict, Some(&mut cut_trace), cancelled)?;
+            Ok(Self::to_tokens(&words, &cut_trace, *part_start))
+        };
+        #[cfg(feature = "parallel")]
+        if parallel {
+            let tokens_of_parts: Vec<Vec<Token>> = parts
+                .par_iter()
+                .zip(part_starts.par_iter())
+                .map(cut_part)
+                .collect::<AnyResult<_>>()?;
+            return Ok(tokens_of_parts.into_iter().flatten().collect());
+        }
+        let tokens_of_parts: Vec<Vec<Token>> = parts
+            .iter()
+            .zip(part_starts.iter())
+            .map(cut_part)
+            .collect::<AnyResult<_>>()?;
+        Ok(tokens_of_parts.into_iter().flatten().collect())
+    }
+
+    /// Convert words cut from a text starting at `offset` into tokens.
+    fn to_tokens(
+        words: &[&CustomStringBytesSlice],
+        cut_trace: &CutTrace,
+        offset: CharacterIndex,
+    ) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::with_capacity(words.len());
+        let mut start: CharacterIndex = 0;
+        for word in words {
+            let end = start + word.chars_len();
+            let kind = match cut_trace.unknown_kinds.get(&start) {
+                Some(kind) => *kind,
+                None => TokenKind::Dictionary,
+            };
+            tokens.push(Token {
+                text: CustomString::convert_raw_bytes_to_std_string(word),
+                start: offset + start,
+                end: offset + end,
+                kind,
+            });
+            start = end;
+        }
+        tokens
+    }
+
     fn bfs_paths_graph(
         graph: &HashMap<CharacterIndex, Vec<CharacterIndex>>,
         start: CharacterIndex,
@@ -170,7 +570,18 @@ impl NewmmTokenizer {
 
     fn one_cut<'a>(
         input: &'a CustomString,
-        custom_dict: &Trie,
+        custom_dict: &LayeredDict,
+    ) -> AnyResult<Vec<&'a CustomStringBytesSlice>> {
+        Self::traced_one_cut(input, custom_dict, None, None)
+    }
+
+    /// Same as `one_cut`, but also records intermediate results into `trace`,
+    /// and stops with a `SegmentCancelled` error once `cancelled` is set.
+    fn traced_one_cut<'a>(
+        input: &'a CustomString,
+        custom_dict: &LayeredDict,
+        mut trace: Option<&mut CutTrace>,
+        cancelled: Option<&AtomicBool>,
     ) -> AnyResult<Vec<&'a CustomStringBytesSlice>> {
         let text = input;
         let input_char_len = text.chars_len();
@@ -196,9 +607,10 @@ impl NewmmTokenizer {
             None => false,
             _ => false,
         } {
+            check_cancelled(cancelled)?;
             if let Some(begin_position) = position_list.pop() {
                 let sub_text_prefix = text.substring(begin_position, text.chars_len());
-                let prefixes = Trie::prefix_ref(&sub_text_prefix, custom_dict);
+                let prefixes = custom_dict.prefix_ref(&sub_text_prefix);
                 for word in prefixes {
                     let word_length = word.chars_len();
                     let end_position_candidate = begin_position + word_length;
@@ -246,7 +658,7 @@ impl NewmmTokenizer {
                     }
                 } else if position_list_length == 0 {
                     // no candidate, deal with non-dict word
-                    match NON_THAI_PATTERN.find(sub_text_prefix.raw_content()) {
+                    let kind = match NON_THAI_PATTERN.find(sub_text_prefix.raw_content()) {
                         // is non-Thai -> skip to the end of match
                         Some(match_point) => {
                             let matched_start_char_index = match_point.start() / BYTES_PER_CHAR;
@@ -259,6 +671,7 @@ impl NewmmTokenizer {
                                         matched_end_char_index,
                                     )
                                     .chars_len();
+                            TokenKind::NonThai
                         }
                         // is Thai -> find min skip
                         None => {
@@ -267,7 +680,7 @@ impl NewmmTokenizer {
                                 if valid_position.contains(&position) {
                                     let prefix = text.substring(position, text_length);
 
-                                    let list_of_prefixes = Trie::prefix_ref(&prefix, custom_dict);
+                                    let list_of_prefixes = custom_dict.prefix_ref(&prefix);
                                     let valid_word_filter = |word: &&[u8]| {
                                         let new_position = position + word.chars_len();
                                         let is_valid = valid_position.contains(&new_position);
@@ -275,18 +688,8 @@ impl NewmmTokenizer {
                                             THAI_TWOCHARS_PATTERN.is_match(word);
                                         is_valid && !is_two_thai_chars
                                     };
-                                    let valid_words: Vec<&[u8]> =
-                                        if list_of_prefixes.len() >= USE_MULTITHREAD_THRESHOLD {
-                                            list_of_prefixes
-                                                .into_par_iter()
-                                                .filter(valid_word_filter)
-                                                .collect()
-                                        } else {
-                                            list_of_prefixes
-                                                .into_iter()
-                                                .filter(valid_word_filter)
-                                                .collect()
-                                        };
+                                    let valid_words =
+                                        filter_words(list_of_prefixes, valid_word_filter);
 
                                     if !valid_words.is_empty() {
                                         end_position = position;
@@ -303,7 +706,11 @@ impl NewmmTokenizer {
                             if finish_without_break {
                                 end_position = text_length;
                             }
+                            TokenKind::Unknown
                         }
+                    };
+                    if let Some(trace) = trace.as_mut() {
+                        trace.unknown_kinds.insert(begin_position, kind);
                     }
 
                     if let Some(existing_path) = graph.get_mut(&begin_position) {
@@ -327,107 +734,80 @@ impl NewmmTokenizer {
                 }
             }
         }
+        if let Some(trace) = trace {
+            trace.tcc_boundaries = valid_position;
+            trace.graph = graph;
+        }
         Ok(result_str)
     }
 
-    fn internal_segment(
+    /// Split a long text into parts at spaces or around the longest word,
+    /// so each part can be segmented separately in safe mode.
+    fn split_safe_parts(
         input: &CustomString,
-        custom_dict: &Trie,
-        safe: bool,
-        parallel: bool,
-    ) -> AnyResult<Vec<String>> {
-        if input.is_empty() {
-            return Ok(vec![]);
-        }
-        if !safe || input.chars_len() < TEXT_SCAN_END {
-            let result = Self::one_cut(input, custom_dict)?;
-            Ok(if parallel {
-                result
-                    .into_par_iter()
-                    .map(|custom_substring| {
-                        CustomString::convert_raw_bytes_to_std_string(custom_substring)
-                    })
-                    .collect()
+        custom_dict: &LayeredDict,
+        cancelled: Option<&AtomicBool>,
+    ) -> AnyResult<Vec<CustomString>> {
+        let mut txt = input.substring(0, input.chars_len());
+        let mut txt_parts: Vec<CustomString> = Vec::with_capacity(txt.chars_len() / 10);
+        while txt.chars_len() >= TEXT_SCAN_END {
+            check_cancelled(cancelled)?;
+            let sample = txt.substring(TEXT_SCAN_BEGIN, TEXT_SCAN_END);
+
+            let mut cut_pos;
+
+            let space_char_index = rfind_space_char_index(sample.raw_content());
+            // there is a space
+            if let Some(space_char_index) = space_char_index {
+                cut_pos = space_char_index + 1;
             } else {
-                result
-                    .into_iter()
-                    .map(|custom_substring| {
-                        CustomString::convert_raw_bytes_to_std_string(custom_substring)
-                    })
-                    .collect()
-            })
-        } else {
-            let mut txt = input.substring(0, input.chars_len());
-            let mut txt_parts: Vec<CustomString> = Vec::with_capacity(txt.chars_len() / 10);
-            while txt.chars_len() >= TEXT_SCAN_END {
-                let sample = txt.substring(TEXT_SCAN_BEGIN, TEXT_SCAN_END);
-
-                let mut cut_pos;
-
-                let space_char_index = rfind_space_char_index(sample.raw_content());
-                // there is a space
-                if let Some(space_char_index) = space_char_index {
-                    cut_pos = space_char_index + 1;
-                } else {
-                    let word_tokens = Self::one_cut(&sample, custom_dict)?;
-                    let mut token_max_index = 0;
-                    let mut token_max_length = 0;
-                    for (idx, token) in word_tokens.iter().enumerate() {
-                        if token.chars_len() >= token_max_length {
-                            token_max_length = token.chars_len();
-                            token_max_index = idx;
-                        }
-                    }
-                    // choose the position that covers longest token
-                    cut_pos = TEXT_SCAN_BEGIN;
-                    for i in 0..token_max_index {
-                        cut_pos = cut_pos + word_tokens.get(i).unwrap().chars_len();
+                let word_tokens = Self::one_cut(&sample, custom_dict)?;
+                let mut token_max_index = 0;
+                let mut token_max_length = 0;
+                for (idx, token) in word_tokens.iter().enumerate() {
+                    if token.chars_len() >= token_max_length {
+                        token_max_length = token.chars_len();
+                        token_max_index = idx;
                     }
                 }
-                txt_parts.push(txt.substring(0, cut_pos));
-                txt = txt.substring(cut_pos, txt.chars_len());
-            }
-            if !txt.is_empty() {
-                txt_parts.push(txt);
+                // choose the position that covers longest token
+                cut_pos = TEXT_SCAN_BEGIN;
+                for i in 0..token_max_index {
+                    cut_pos = cut_pos + word_tokens.get(i).unwrap().chars_len();
+                }
             }
-
-            Ok(if parallel {
-                txt_parts
-                    .par_iter()
-                    .flat_map(|part| -> AnyResult<_> {
-                        let bind_part = &part.substring(0, part.chars_len());
-                        let words = Self::one_cut(bind_part, custom_dict)?;
-                        Ok(words
-                            .into_par_iter()
-                            .map(CustomString::convert_raw_bytes_to_std_string)
-                            .collect::<Vec<String>>())
-                    })
-                    .flatten()
-                    .collect()
-            } else {
-                txt_parts
-                    .iter()
-                    .flat_map(|part| -> AnyResult<_> {
-                        Ok(
-                            Self::one_cut(&part.substring(0, part.chars_len()), custom_dict)?
-                                .iter()
-                                .map(|word| CustomString::convert_raw_bytes_to_std_string(word))
-                                .collect::<Vec<String>>(),
-                        )
-                    })
-                    .flatten()
-                    .collect()
-            })
+            txt_parts.push(txt.substring(0, cut_pos));
+            txt = txt.substring(cut_pos, txt.chars_len());
         }
+        if !txt.is_empty() {
+            txt_parts.push(txt);
+        }
+        Ok(txt_parts)
     }
 }
 
 impl Tokenizer for NewmmTokenizer {
     fn segment(&self, text: &str, safe: bool, parallel: bool) -> AnyResult<Vec<String>> {
-        Self::internal_segment(&CustomString::new(text), &self.dict, safe, parallel)
+        Ok(self
+            .segment_to_tokens(text, safe, parallel)?
+            .into_iter()
+            .map(|token| token.text)
+            .collect())
     }
 
     fn segment_to_string(&self, text: &str, safe: bool, parallel: bool) -> Vec<String> {
         self.segment(text, safe, parallel).unwrap()
     }
 }
+
+#[test]
+fn test_filter_words_above_multithread_threshold() {
+    // more words than USE_MULTITHREAD_THRESHOLD, filtered on multiple threads
+    // with the "parallel" feature; the order of the words is kept
+    let words: Vec<Vec<u8>> = (0..25_000_u32).map(|i| i.to_le_bytes().to_vec()).collect();
+    let words: Vec<&[u8]> = words.iter().map(|word| word.as_slice()).collect();
+    let is_even = |word: &&[u8]| word[0] % 2 == 0;
+    let expected: Vec<&[u8]> = words.iter().copied().filter(is_even).collect();
+    assert_eq!(filter_words(words.clone(), is_even), expected);
+    assert_eq!(filter_words(words[..10].to_vec(), is_even), expected[..5]);
+}
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * A tokenizer handle that can be shared by threads and updated while in use.
+ *
+ * Readers segment against an immutable snapshot of the tokenizer.
+ * Writers build a new version and publish it atomically;
+ * a segmentation already running keeps the snapshot it started with.
+*/
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{dict_reader::DictSource, newmm::NewmmTokenizer, tokenizer_trait::Tokenizer};
+use anyhow::Result as AnyResult;
+use arc_swap::ArcSwap;
+
+#[derive(Debug)]
+pub struct SharedNewmmTokenizer {
+    current: ArcSwap<NewmmTokenizer>,
+}
+
+impl SharedNewmmTokenizer {
+    pub fn new(tokenizer: NewmmTokenizer) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(tokenizer),
+        }
+    }
+
+    /// The current version of the tokenizer.
+    /// It is not affected by later updates.
+    pub fn snapshot(&self) -> Arc<NewmmTokenizer> {
+        self.current.load_full()
+    }
+
+    /// Replace the tokenizer with a new version.
+    pub fn store(&self, tokenizer: NewmmTokenizer) {
+        self.current.store(Arc::new(tokenizer));
+    }
+
+    /// Publish a modified copy of the current version.
+    /// `update` may be called more than once if other writers publish meanwhile.
+    ///
+    /// Copying is cheap: the dictionary layers are shared
+    /// and only the small top layer is copied when modified.
+    pub fn update<F: Fn(&mut NewmmTokenizer)>(&self, update: F) {
+        self.current.rcu(|current| {
+            let mut tokenizer = NewmmTokenizer::clone(current);
+            update(&mut tokenizer);
+            tokenizer
+        });
+    }
+
+    /// Add words to the dictionary, in a new version.
+    pub fn add_word(&self, word_list: &[&str]) {
+        self.update(|tokenizer| tokenizer.add_word(word_list));
+    }
+
+    /// Remove words from the dictionary, in a new version.
+    pub fn remove_word(&self, word_list: &[&str]) {
+        self.update(|tokenizer| tokenizer.remove_word(word_list));
+    }
+
+    /// Reload the dictionary from a text file, one word per line.
+    /// Words added or removed before are discarded, the normalizer is kept.
+    /// On error, the current version is kept.
+    pub fn reload_from_file<P: AsRef<Path>>(&self, dict_path: P) -> AnyResult<()> {
+        let reloaded =
+            NewmmTokenizer::from_dict_source(DictSource::FilePath(dict_path.as_ref().into()))?;
+        self.current.rcu(|current| {
+            let mut tokenizer = reloaded.clone();
+            tokenizer.set_normalizer(current.normalizer().cloned());
+            tokenizer
+        });
+        Ok(())
+    }
+}
+
+impl Tokenizer for SharedNewmmTokenizer {
+    fn segment(&self, text: &str, safe: bool, parallel: bool) -> AnyResult<Vec<String>> {
+        self.current.load().segment(text, safe, parallel)
+    }
+
+    fn segment_to_string(&self, text: &str, safe: bool, parallel: bool) -> Vec<String> {
+        self.segment(text, safe, parallel).unwrap()
+    }
+}
@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2026 PyThaiNLP Project
+// SPDX-License-Identifier: Apache-2.0
+
+/*!
+ * Tokens with their positions in the input text.
+*/
+
+/// How a token was found by the tokenizer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TokenKind {
+    /// A word from the dictionary
+    Dictionary,
+    /// Not in the dictionary, matched as non-Thai text
+    /// (Latin letters, digits, spaces, or a line break)
+    NonThai,
+    /// Thai text not in the dictionary, cut at the nearest possible word boundary
+    Unknown,
+}
+
+/// A token and its position in the input text.
+///
+/// `start` and `end` are character (Unicode scalar value) indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Token {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub kind: TokenKind,
+}
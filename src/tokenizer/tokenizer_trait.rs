@@ -4,6 +4,11 @@
 use anyhow::Result as AnyResult;
 
 pub trait Tokenizer {
+    /// Segment text into words.
+    ///
+    /// `safe` avoids long run times on text with many ambiguous word boundaries.
+    /// `parallel` segments on multiple threads; it is ignored
+    /// when the crate is built without the "parallel" feature.
     fn segment(&self, text: &str, safe: bool, parallel: bool) -> AnyResult<Vec<String>>;
 
     fn segment_to_string(&self, text: &str, safe: bool, parallel: bool) -> Vec<String>;
@@ -20,7 +20,7 @@ use crate::four_bytes_str::custom_string::{
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::borrow::BorrowMut;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct TrieNode {
     ///
     children: HashMap<char, Self>,
@@ -72,6 +72,43 @@ impl TrieNode {
             .add_word(&input_word.substring(1, input_word.chars_len()));
     }
 
+    /// Push `word` followed by each word below this node that `keep` accepts
+    /// to `words`, in code point order, until `words` has `limit` words.
+    fn collect_words<F: FnMut(&str) -> bool>(
+        &self,
+        word: &mut String,
+        words: &mut Vec<String>,
+        limit: usize,
+        keep: &mut F,
+    ) {
+        if words.len() >= limit {
+            return;
+        }
+        if self.end && keep(word) {
+            words.push(word.clone());
+        }
+        let mut children: Vec<(&char, &Self)> = self.children.iter().collect();
+        children.sort_unstable_by_key(|(character, _)| **character);
+        for (character, child) in children {
+            if words.len() >= limit {
+                break;
+            }
+            word.push(*character);
+            child.collect_words(word, words, limit, keep);
+            word.pop();
+        }
+    }
+
+    fn visit<F: FnMut(&[char], bool) -> bool>(&self, path: &mut Vec<char>, visitor: &mut F) {
+        for (character, child) in self.children.iter() {
+            path.push(*character);
+            if visitor(path, child.end) {
+                child.visit(path, visitor);
+            }
+            path.pop();
+        }
+    }
+
     fn remove_word(&mut self, input_word: &CustomString) {
         let mut word = input_word;
         let char_count = word.chars_len();
@@ -94,7 +131,7 @@ impl TrieNode {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 /// This version of Trie still stores custom bytes vector as words,
 /// but prefix operation and its node uses char instead.
 pub struct TrieChar {
@@ -135,10 +172,12 @@ impl TrieChar {
             self.root.remove_word(&stripped_word); // remove from node
         }
     }
-    #[allow(dead_code)]
     pub fn contain(&self, word: &CustomString) -> bool {
         self.words.contains(word.raw_content())
     }
+    pub fn contain_raw(&self, word: &CustomStringBytesSlice) -> bool {
+        self.words.contains(word)
+    }
     #[allow(dead_code)]
     pub fn iterate(&self) -> std::collections::hash_set::Iter<'_, Vec<u8>> {
         self.words.iter()
@@ -147,6 +186,41 @@ impl TrieChar {
     pub fn amount_of_words(&self) -> usize {
         self.words.len()
     }
+    /// Words starting with `prefix`, including `prefix` itself, in code point order.
+    /// The walk stops after the first `limit` words.
+    pub fn words_with_prefix(&self, prefix: &CustomString, limit: Option<usize>) -> Vec<String> {
+        self.words_with_prefix_filtered(prefix, limit, |_| true)
+    }
+    /// Like `words_with_prefix`, with only the words that `keep` accepts.
+    pub fn words_with_prefix_filtered<F: FnMut(&str) -> bool>(
+        &self,
+        prefix: &CustomString,
+        limit: Option<usize>,
+        mut keep: F,
+    ) -> Vec<String> {
+        let mut node = &self.root;
+        for character in prefix.get_chars_content() {
+            match node.find_child(character) {
+                Some(child) => node = child,
+                None => return vec![],
+            }
+        }
+        let mut words = vec![];
+        let mut word: String = prefix.get_chars_content().iter().collect();
+        node.collect_words(
+            &mut word,
+            &mut words,
+            limit.unwrap_or(usize::MAX),
+            &mut keep,
+        );
+        words
+    }
+    /// Visit the nodes depth first, in no particular order.
+    /// `visitor` gets the characters from the root to the node and whether
+    /// they are a word, and returns whether to visit the node's children.
+    pub fn visit<F: FnMut(&[char], bool) -> bool>(&self, mut visitor: F) {
+        self.root.visit(&mut vec![], &mut visitor);
+    }
     /// Returns a vec of substring (as reference) as produced by words stored in dict_trie.
     pub fn prefix_ref<'p>(
         prefix: &'p CustomString,
@@ -196,3 +270,40 @@ fn test_add_and_remove_word() {
     trie.remove(&CustomString::new(""));
     assert_eq!(trie.amount_of_words(), 0);
 }
+
+#[test]
+fn test_words_with_prefix() {
+    let trie = TrieChar::new(&[
+        CustomString::new("ศาลา"),
+        CustomString::new("ศาล"),
+        CustomString::new("ศาสนา"),
+        CustomString::new("ภาษา"),
+    ]);
+    assert_eq!(
+        trie.words_with_prefix(&CustomString::new("ศา"), None),
+        ["ศาล", "ศาลา", "ศาสนา"]
+    );
+    assert_eq!(
+        trie.words_with_prefix(&CustomString::new("ศาล"), None),
+        ["ศาล", "ศาลา"]
+    );
+    assert!(trie
+        .words_with_prefix(&CustomString::new("ศาลาวัด"), None)
+        .is_empty());
+    let all_words = trie.words_with_prefix(&CustomString::new(""), None);
+    assert_eq!(all_words.len(), 4);
+
+    // the first words in code point order
+    assert_eq!(
+        trie.words_with_prefix(&CustomString::new(""), Some(2)),
+        ["ภาษา", "ศาล"]
+    );
+    assert!(trie
+        .words_with_prefix(&CustomString::new("ศา"), Some(0))
+        .is_empty());
+    let prefix = CustomString::new("ศา");
+    assert_eq!(
+        trie.words_with_prefix_filtered(&prefix, Some(1), |word| word != "ศาล"),
+        ["ศาลา"]
+    );
+}
@@ -4,7 +4,11 @@
 /**
  * Test the NewmmTokenizer with the default dictionary.
  */
-use nlpo3::tokenizer::newmm::NewmmTokenizer;
+use std::sync::atomic::AtomicBool;
+
+use nlpo3::tokenizer::newmm::{NewmmTokenizer, SegmentCancelled};
+use nlpo3::tokenizer::shared::SharedNewmmTokenizer;
+use nlpo3::tokenizer::token::TokenKind;
 use nlpo3::tokenizer::tokenizer_trait::Tokenizer;
 
 const FIRST_TEXT: &str = "นิสสันผ่อนจนเพลียนาวาร่า..";
@@ -180,6 +184,22 @@ fn test_long_text_byte_tokenizer() {
     let safe_result = tokenizer.segment(&text, true, true).unwrap();
     assert_eq!(result.len(), 1889);
     assert_eq!(safe_result.len(), 1991);
+    assert_eq!(safe_result.concat(), text);
+
+    let safe_tokens = tokenizer.segment_to_tokens(&text, true, true).unwrap();
+    assert_eq!(
+        safe_tokens
+            .iter()
+            .map(|token| token.text.clone())
+            .collect::<Vec<String>>(),
+        safe_result
+    );
+    let mut position = 0;
+    for token in safe_tokens.iter() {
+        assert_eq!(token.start, position);
+        position = token.end;
+    }
+    assert_eq!(position, text.chars().count());
 }
 
 #[test]
@@ -236,6 +256,28 @@ fn test_add_or_remove_word() {
     );
 }
 
+#[test]
+fn test_trace() {
+    let tokenizer = NewmmTokenizer::from_word_list(vec!["กิน".to_string(), "ข้าว".to_string()]);
+    let trace = tokenizer.trace("กินข้าวabcสวย").unwrap();
+    assert_eq!(
+        trace
+            .tokens
+            .iter()
+            .map(|token| (token.text.as_str(), token.start, token.end, token.kind))
+            .collect::<Vec<_>>(),
+        [
+            ("กิน", 0, 3, TokenKind::Dictionary),
+            ("ข้าว", 3, 7, TokenKind::Dictionary),
+            ("abc", 7, 10, TokenKind::NonThai),
+            ("สวย", 10, 13, TokenKind::Unknown),
+        ]
+    );
+    assert_eq!(trace.tcc_boundaries, [2, 3, 6, 7, 8, 9, 10, 11, 12, 13]);
+    assert_eq!(trace.graph, [(0, 3), (3, 7), (7, 10), (10, 13)]);
+    assert!(tokenizer.trace("").unwrap().tokens.is_empty());
+}
+
 #[test]
 fn test_with_some_real_data() {
     let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
@@ -305,3 +347,414 @@ fn test_thai_number() {
         ["USD", "๑,๙๘๔.๔๒"]
     );
 }
+
+#[test]
+fn test_tokenizer_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<NewmmTokenizer>();
+}
+
+#[test]
+fn test_from_bytes() {
+    let tokenizer = NewmmTokenizer::from_bytes("กิน\r\nข้าว\n\n".as_bytes()).unwrap();
+    assert_eq!(
+        tokenizer.segment("กินข้าว", false, false).unwrap(),
+        ["กิน", "ข้าว"]
+    );
+    assert!(NewmmTokenizer::from_bytes(&[0xff, b'\n']).is_err());
+}
+
+#[test]
+fn test_parallel_flag_gives_same_tokens() {
+    // In safe mode, the flag cuts the parts of the text on multiple threads.
+    // Without the "parallel" feature, the flag is ignored.
+    let tokenizer = NewmmTokenizer::from_word_list(vec![
+        "ห้องสมุด".to_string(),
+        "ประชาชน".to_string(),
+        "กิน".to_string(),
+        "ข้าว".to_string(),
+    ]);
+    let text = "ห้องสมุดประชาชน กินข้าว\n".repeat(50);
+    assert_eq!(
+        tokenizer.segment(&text, false, true).unwrap(),
+        tokenizer.segment(&text, false, false).unwrap()
+    );
+    assert_eq!(
+        tokenizer.segment_to_tokens(&text, true, true).unwrap(),
+        tokenizer.segment_to_tokens(&text, true, false).unwrap()
+    );
+}
+
+#[test]
+fn test_segment_cancellable() {
+    let tokenizer = NewmmTokenizer::from_word_list(vec!["กิน".to_string(), "ข้าว".to_string()]);
+    // a long text without spaces or newlines, cut into parts in safe mode
+    let text = "กินข้าว".repeat(100);
+    let not_cancelled = AtomicBool::new(false);
+    assert_eq!(
+        tokenizer
+            .segment_to_tokens_cancellable(&text, true, false, &not_cancelled)
+            .unwrap(),
+        tokenizer.segment_to_tokens(&text, true, false).unwrap()
+    );
+
+    let cancelled = AtomicBool::new(true);
+    for (safe, parallel) in [(true, false), (true, true), (false, false)] {
+        let error = tokenizer
+            .segment_to_tokens_cancellable(&text, safe, parallel, &cancelled)
+            .unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&SegmentCancelled));
+    }
+}
+
+#[test]
+fn test_segment_to_byte_spans() {
+    let tokenizer = NewmmTokenizer::from_word_list(vec!["กิน".to_string(), "ข้าว".to_string()]);
+    let text = "กินข้าว a";
+    let spans: Vec<(String, &str)> = tokenizer
+        .segment_to_byte_spans(text, false, false)
+        .unwrap()
+        .into_iter()
+        .map(|(token, span)| (token.text, &text[span]))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("กิน".to_string(), "กิน"),
+            ("ข้าว".to_string(), "ข้าว"),
+            (" ".to_string(), " "),
+            ("a".to_string(), "a")
+        ]
+    );
+    assert!(tokenizer
+        .segment_to_byte_spans("", false, false)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_with_overlay() {
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DEFAULT_DICT_PATH);
+
+    let base = NewmmTokenizer::new(&relative_dict_path);
+    let mut overlay = base.with_overlay();
+    overlay.add_word(&["ห้องสมุดประชาชนเทศบาลตำบลวิชิต"]);
+    assert_eq!(
+        overlay.segment_to_string("ห้องสมุดประชาชนเทศบาลตำบลวิชิต", false, false),
+        ["ห้องสมุดประชาชนเทศบาลตำบลวิชิต"]
+    );
+    overlay.remove_word(&["ห้องสมุดประชาชนเทศบาลตำบลวิชิต", "ห้องสมุดประชาชน", "ประชาชน"]);
+    assert_eq!(
+        overlay.segment_to_string("ห้องสมุดประชาชนเทศบาลตำบลวิชิต", false, false),
+        ["ห้องสมุด", "ประชา", "ชน", "เทศบาลตำบล", "วิชิต"]
+    );
+    assert_eq!(overlay.dict_layer_count(), 2);
+
+    // the base dictionary is shared, not modified
+    assert_eq!(
+        base.segment_to_string("ห้องสมุดประชาชนเทศบาลตำบลวิชิต", false, false),
+        ["ห้องสมุดประชาชน", "เทศบาลตำบล", "วิชิต"]
+    );
+    assert_eq!(base.dict_layer_count(), 1);
+}
+
+#[test]
+fn test_shared_tokenizer_updates() {
+    let shared = std::sync::Arc::new(SharedNewmmTokenizer::new(NewmmTokenizer::from_word_list(
+        vec!["กิน".to_string(), "ข้าว".to_string()],
+    )));
+    let snapshot = shared.snapshot();
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = std::sync::Arc::clone(&shared);
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    let tokens = shared.segment("กินข้าว", false, false).unwrap();
+                    assert!(tokens == ["กิน", "ข้าว"] || tokens == ["กินข้าว"]);
+                }
+            })
+        })
+        .collect();
+    shared.add_word(&["กินข้าว"]);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(shared.segment_to_string("กินข้าว", false, false), ["กินข้าว"]);
+    // a snapshot is not affected by later updates
+    assert_eq!(
+        snapshot.segment_to_string("กินข้าว", false, false),
+        ["กิน", "ข้าว"]
+    );
+
+    shared.remove_word(&["กินข้าว", "ข้าว"]);
+    assert_eq!(
+        shared.segment_to_string("กินข้าว", false, false),
+        ["กิน", "ข้าว"]
+    );
+    assert_eq!(shared.snapshot().dict_layer_count(), 2);
+}
+
+#[test]
+fn test_shared_tokenizer_reload_from_file() {
+    use nlpo3::normalize::Normalizer;
+
+    let shared =
+        SharedNewmmTokenizer::new(NewmmTokenizer::from_word_list(vec!["กินข้าว".to_string()]));
+    assert!(shared.reload_from_file("no/such/file.txt").is_err());
+    assert_eq!(shared.segment_to_string("กินข้าว", false, false), ["กินข้าว"]);
+
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DEFAULT_DICT_PATH);
+    shared.reload_from_file(&relative_dict_path).unwrap();
+    assert_eq!(
+        shared.segment_to_string("ห้องสมุดประชาชน", false, false),
+        ["ห้องสมุดประชาชน"]
+    );
+
+    // the normalizer is kept
+    shared.update(|tokenizer| tokenizer.set_normalizer(Some(Normalizer::new())));
+    shared.reload_from_file(&relative_dict_path).unwrap();
+    assert_eq!(shared.snapshot().normalizer(), Some(&Normalizer::new()));
+    assert_eq!(
+        shared.segment_to_string("ห้องสมุดดดดประชาชน", false, false),
+        ["ห้องสมุดประชาชน"]
+    );
+}
+
+#[test]
+fn test_extended_dict_format() {
+    use nlpo3::tokenizer::WordInfo;
+
+    const DICT_PATH: &str = "/tests/data/dict_extended.txt";
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DICT_PATH);
+    let mut tokenizer = NewmmTokenizer::new(&relative_dict_path);
+    assert_eq!(
+        tokenizer.word_info("กิน"),
+        Some(WordInfo {
+            frequency: Some(1200),
+            pos: Some("VACT".to_string()),
+            ..WordInfo::default()
+        })
+    );
+    assert_eq!(tokenizer.word_info("กินข้าว"), Some(WordInfo::default()));
+    assert_eq!(tokenizer.word_info("ปลา"), None);
+    assert_eq!(
+        tokenizer.segment_to_string("นายกรัฐมนตรี", false, false),
+        ["นายกรัฐมนตรี"]
+    );
+
+    tokenizer.add_word_with_info(
+        "กินข้าว",
+        WordInfo {
+            no_prefix: true,
+            ..WordInfo::default()
+        },
+    );
+    assert_eq!(
+        tokenizer.segment_to_string("กินข้าว", false, false),
+        ["กิน", "ข้าว"]
+    );
+    assert!(tokenizer.word_info("กินข้าว").unwrap().no_prefix);
+}
+
+#[cfg(all(feature = "gzip", feature = "zstd"))]
+#[test]
+fn test_compressed_dict() {
+    use nlpo3::tokenizer::DictSource;
+    use std::fs::{self, File};
+    use std::io::BufReader;
+    use std::path::PathBuf;
+
+    let data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let plain =
+        NewmmTokenizer::from_bytes(&fs::read(data_dir.join("dict_extended.txt")).unwrap()).unwrap();
+    for file_name in ["dict_extended.txt.gz", "dict_extended.txt.zst"] {
+        let path = data_dir.join(file_name);
+        let tokenizers = [
+            NewmmTokenizer::from_dict_source(DictSource::FilePath(path.clone())).unwrap(),
+            NewmmTokenizer::from_bytes(&fs::read(&path).unwrap()).unwrap(),
+            NewmmTokenizer::from_reader(BufReader::new(File::open(&path).unwrap())).unwrap(),
+        ];
+        for tokenizer in tokenizers.iter() {
+            assert_eq!(tokenizer.word_info("กิน"), plain.word_info("กิน"));
+            assert_eq!(
+                tokenizer.segment_to_string("นายกรัฐมนตรีกินข้าว", false, false),
+                plain.segment_to_string("นายกรัฐมนตรีกินข้าว", false, false)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_dictionary_queries() {
+    use nlpo3::tokenizer::WordOrder;
+
+    const DICT_PATH: &str = "/tests/data/dict_extended.txt";
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DICT_PATH);
+    let mut tokenizer = NewmmTokenizer::new(&relative_dict_path);
+    tokenizer.add_word(&["กินเล่น"]);
+    assert!(tokenizer.contains_word("กิน"));
+    assert!(!tokenizer.contains_word("กา"));
+    assert_eq!(
+        tokenizer.words_with_prefix("กิน", None, WordOrder::Alphabetical),
+        ["กิน", "กินข้าว", "กินเล่น"]
+    );
+    assert_eq!(
+        tokenizer.words_with_prefix("กิน", Some(1), WordOrder::Frequency),
+        ["กิน"]
+    );
+    assert!(tokenizer
+        .words_with_prefix("ปลา", None, WordOrder::Alphabetical)
+        .is_empty());
+    // queries are trimmed like the words of the dictionary
+    assert_eq!(
+        tokenizer.words_with_prefix(" กินข", None, WordOrder::Alphabetical),
+        ["กินข้าว"]
+    );
+
+    let words: Vec<String> = tokenizer.words().collect();
+    let mut sorted = words.clone();
+    sorted.sort();
+    assert_eq!(words, sorted);
+    assert!(words.contains(&"กินเล่น".to_string()));
+}
+
+#[test]
+fn test_fuzzy_lookup() {
+    use nlpo3::tokenizer::fuzzy::EditCosts;
+
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DEFAULT_DICT_PATH);
+    let tokenizer = NewmmTokenizer::new(&relative_dict_path);
+    let costs = EditCosts::default();
+
+    // wrong tone mark, and a consonant of the same sound
+    let matches = tokenizer.fuzzy_lookup("ไก้", 0.5, &costs);
+    assert!(matches.iter().any(|found| found.word == "ไก่"));
+    let matches = tokenizer.fuzzy_lookup("สาลา", 0.5, &costs);
+    assert_eq!(matches[0].word, "ศาลา");
+    assert_eq!(matches[0].distance, 0.5);
+
+    // closest first
+    let matches = tokenizer.fuzzy_lookup("ศาลา", 1.0, &costs);
+    assert_eq!(matches[0].word, "ศาลา");
+    assert_eq!(matches[0].distance, 0.0);
+    assert!(matches
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+    assert!(matches.iter().all(|found| found.distance <= 1.0));
+
+    // queries are trimmed like the words of the dictionary
+    for query in [" ศาลา\n", "\u{180E}ศาลา"] {
+        assert!(tokenizer.contains_word(query));
+        assert_eq!(tokenizer.fuzzy_lookup(query, 0.0, &costs)[0].word, "ศาลา");
+    }
+}
+
+#[test]
+fn test_normalizer() {
+    use nlpo3::normalize::Normalizer;
+
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DEFAULT_DICT_PATH);
+    let mut tokenizer = NewmmTokenizer::new(&relative_dict_path);
+    // decomposed sara am, zero-width space, repeated consonant
+    let text = "กินน\u{0E4D}\u{0E49}า\u{200B}เย็นมากกกกก";
+    assert_ne!(
+        tokenizer.segment(text, false, false).unwrap(),
+        ["กิน", "น้ำเย็น", "มาก"]
+    );
+
+    tokenizer.set_normalizer(Some(Normalizer::new()));
+    assert!(tokenizer.normalizer().is_some());
+    assert_eq!(
+        tokenizer.segment(text, false, false).unwrap(),
+        ["กิน", "น้ำเย็น", "มาก"]
+    );
+    let tokens = tokenizer.segment_to_tokens(text, false, false).unwrap();
+    let spans: Vec<(usize, usize)> = tokens
+        .iter()
+        .map(|token| (token.start, token.end))
+        .collect();
+    assert_eq!(spans, [(0, 3), (3, 12), (12, 19)]);
+    assert_eq!(tokens[1].text, "น้ำเย็น");
+    assert!(tokens
+        .iter()
+        .all(|token| token.kind == TokenKind::Dictionary));
+
+    // trace segments the normalized text, with positions in the original text
+    let trace = tokenizer.trace(text).unwrap();
+    assert_eq!(trace.tokens, tokens);
+    assert_eq!(trace.tcc_boundaries.last(), Some(&19));
+    assert!(trace.graph.contains(&(3, 12)));
+
+    // an overlay keeps the normalizer
+    assert!(tokenizer.with_overlay().normalizer().is_some());
+    tokenizer.set_normalizer(None);
+    assert_ne!(
+        tokenizer.segment(text, false, false).unwrap(),
+        ["กิน", "น้ำเย็น", "มาก"]
+    );
+}
+
+/// Replace `\u{...}` escapes of a line of a test corpus.
+fn unescape(line: &str) -> String {
+    let mut unescaped = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(escape_start) = rest.find("\\u{") {
+        unescaped.push_str(&rest[..escape_start]);
+        let code_end = escape_start + rest[escape_start..].find('}').unwrap();
+        let code = u32::from_str_radix(&rest[escape_start + 3..code_end], 16).unwrap();
+        unescaped.push(char::from_u32(code).unwrap());
+        rest = &rest[code_end + 1..];
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[test]
+fn test_sanitize_corpus() {
+    use nlpo3::normalize::{Normalizer, Sanitizer, UnicodeForm};
+
+    const CORPUS_PATH: &str = "/tests/data/sanitize_corpus.txt";
+    let mut relative_corpus_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_corpus_path.push_str(CORPUS_PATH);
+    let corpus = std::fs::read_to_string(relative_corpus_path).unwrap();
+    let mut relative_dict_path = env!("CARGO_MANIFEST_DIR").to_string();
+    relative_dict_path.push_str(DEFAULT_DICT_PATH);
+    let mut tokenizer = NewmmTokenizer::new(&relative_dict_path);
+
+    for line in corpus.lines().filter(|line| !line.starts_with('#')) {
+        let columns: Vec<&str> = line.split('\t').collect();
+        let form = match columns[0] {
+            "nfc" => UnicodeForm::Nfc,
+            "nfkc" => UnicodeForm::Nfkc,
+            other => panic!("unknown form: {}", other),
+        };
+        let (input, expected) = (unescape(columns[1]), unescape(columns[2]));
+        let sanitizer = Sanitizer::new().form(Some(form));
+        let sanitized = sanitizer.sanitize(&input);
+        assert_eq!(sanitized.as_str(), expected, "{:?}", input);
+        let input_len = input.chars().count();
+        assert_eq!(
+            sanitized.original_span(0, sanitized.chars_len()),
+            (0, input_len)
+        );
+
+        // same tokens as the visible text, with spans in the input
+        let normalizer = Normalizer::new()
+            .sanitizer(Some(sanitizer))
+            .remove_zero_width(false);
+        tokenizer.set_normalizer(Some(normalizer));
+        let tokens = tokenizer.segment_to_tokens(&input, false, false).unwrap();
+        let words: Vec<String> = tokens.iter().map(|token| token.text.clone()).collect();
+        tokenizer.set_normalizer(None);
+        assert_eq!(words, tokenizer.segment(&expected, false, false).unwrap());
+        assert_eq!(tokens.first().unwrap().start, 0);
+        assert_eq!(tokens.last().unwrap().end, input_len);
+        assert!(tokens.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+}